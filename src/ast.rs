@@ -1,7 +1,16 @@
+use std::{borrow::Cow, fmt, time::UNIX_EPOCH};
+
 use lalrpop_util::lalrpop_mod;
 use num_runtime_fmt::NumFmt;
 
-use crate::{Context, Result, Value, ValueError};
+use crate::{
+    types::Calcable, value::datetime, Backend, Context, MachineType, Result, Value, ValueError,
+};
+
+/// Maximum nesting depth of user-defined function calls.
+///
+/// This guards against runaway recursion exhausting the stack.
+const MAX_CALL_DEPTH: usize = 64;
 
 // no point getting style warnings for generated code
 lalrpop_mod!(#[allow(clippy::all)] pub parser);
@@ -13,6 +22,12 @@ pub enum ParseError {
     Index(#[source] std::num::ParseIntError),
     #[error("failed to parse format string")]
     Format(#[from] num_runtime_fmt::parse::Error),
+    #[error("parameter \"{0}\" appears more than once")]
+    DuplicateParameter(String),
+    #[error("unknown type \"{0}\"")]
+    UnknownType(String),
+    #[error("literal {0} is not an integer, so it cannot have an integer type")]
+    IntegerSuffix(String),
 }
 
 /// A prefix operator.
@@ -23,56 +38,158 @@ pub enum PrefixOperator {
 }
 
 impl PrefixOperator {
-    fn evaluate(&self, operand: Value) -> Result {
-        match self {
-            Self::Negation => Ok(-operand),
-            Self::Not => !operand,
-        }
+    fn evaluate<N: Calcable>(&self, operand: N, backend: Backend) -> Result<N> {
+        N::prefix(*self, operand, backend)
     }
 }
 
-/// An infix operator.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// An infix operator, which converts to its symbol: `+`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::IntoStaticStr)]
 pub enum InfixOperator {
+    #[strum(serialize = "+")]
     Add,
+    #[strum(serialize = "-")]
     Sub,
+    #[strum(serialize = "*")]
     Mul,
+    #[strum(serialize = "/")]
     Div,
+    #[strum(serialize = "//")]
     TruncDiv,
+    #[strum(serialize = "**")]
     Pow,
+    #[strum(serialize = "%")]
     Rem,
+    #[strum(serialize = "<<")]
     Lshift,
+    #[strum(serialize = ">>")]
     Rshift,
+    #[strum(serialize = "<<<")]
     RotateL,
+    #[strum(serialize = ">>>")]
     RotateR,
+    #[strum(serialize = "&")]
     BitAnd,
+    #[strum(serialize = "|")]
     BitOr,
+    #[strum(serialize = "^")]
     BitXor,
+    #[strum(serialize = "==")]
+    Eq,
+    #[strum(serialize = "!=")]
+    Ne,
+    #[strum(serialize = "<")]
+    Lt,
+    #[strum(serialize = "<=")]
+    Le,
+    #[strum(serialize = ">")]
+    Gt,
+    #[strum(serialize = ">=")]
+    Ge,
+    #[strum(serialize = "&&")]
+    And,
+    #[strum(serialize = "||")]
+    Or,
 }
 
 impl InfixOperator {
-    fn evaluate(&self, left: Value, right: Value) -> Result {
+    /// For the logical operators, the result if it is already determined by the left operand.
+    ///
+    /// When this returns `Some`, the right operand is not evaluated.
+    fn short_circuit<N: Calcable>(&self, left: &N) -> Option<N> {
+        match self {
+            Self::And if !left.is_truthy() => Some(false.into()),
+            Self::Or if left.is_truthy() => Some(true.into()),
+            _ => None,
+        }
+    }
+
+    fn evaluate<N: Calcable>(&self, left: N, right: N, backend: Backend) -> Result<N> {
+        use std::cmp::Ordering::{Equal, Greater, Less};
+
         match self {
-            Self::Add => Ok(left + right),
-            Self::Sub => Ok(left - right),
-            Self::Mul => Ok(left * right),
-            Self::Div => Ok(left / right),
-            Self::TruncDiv => Ok(left.trunc_div(right)),
-            Self::Rem => Ok(left % right),
-            Self::Pow => left.pow(right),
-            Self::Lshift => left << right,
-            Self::Rshift => left >> right,
-            Self::RotateL => left.rotate_left(right),
-            Self::RotateR => left.rotate_right(right),
-            Self::BitAnd => left & right,
-            Self::BitOr => left | right,
-            Self::BitXor => left ^ right,
+            Self::Eq => Ok((left == right).into()),
+            Self::Ne => Ok((left != right).into()),
+            Self::Lt => Ok(matches!(left.numeric_cmp(&right), Some(Less)).into()),
+            Self::Le => Ok(matches!(left.numeric_cmp(&right), Some(Less | Equal)).into()),
+            Self::Gt => Ok(matches!(left.numeric_cmp(&right), Some(Greater)).into()),
+            Self::Ge => Ok(matches!(left.numeric_cmp(&right), Some(Greater | Equal)).into()),
+            Self::And | Self::Or => Ok(right.is_truthy().into()),
+            _ => N::infix(*self, left, right, backend),
         }
     }
+
+    /// Whether the result of this operator has the type of its operands, as with `+` or `&`.
+    fn is_arithmetic(&self) -> bool {
+        !matches!(
+            self,
+            Self::Eq | Self::Ne | Self::Lt | Self::Le | Self::Gt | Self::Ge | Self::And | Self::Or
+        )
+    }
+
+    /// Whether the right operand of this operator may have a different type from the left,
+    /// as with Rust's `pow` and shifts.
+    fn is_untyped_right(&self) -> bool {
+        matches!(
+            self,
+            Self::Pow | Self::Lshift | Self::Rshift | Self::RotateL | Self::RotateR
+        )
+    }
 }
 
-/// A function name.
+/// The number of arguments a function accepts.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Arity {
+    pub min: usize,
+    /// `None` if the function accepts any number of arguments from `min` up.
+    pub max: Option<usize>,
+}
+
+impl Arity {
+    pub const fn exactly(n: usize) -> Self {
+        Self {
+            min: n,
+            max: Some(n),
+        }
+    }
+
+    pub const fn between(min: usize, max: usize) -> Self {
+        Self {
+            min,
+            max: Some(max),
+        }
+    }
+
+    pub const fn at_least(min: usize) -> Self {
+        Self { min, max: None }
+    }
+
+    /// Whether a call with `n` arguments is acceptable.
+    pub fn accepts(&self, n: usize) -> bool {
+        n >= self.min && self.max.is_none_or(|max| n <= max)
+    }
+}
+
+impl From<usize> for Arity {
+    fn from(n: usize) -> Self {
+        Self::exactly(n)
+    }
+}
+
+impl fmt::Display for Arity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = |n: usize| if n == 1 { "" } else { "s" };
+        match self.max {
+            Some(max) if max == self.min => write!(f, "{max} argument{}", plural(max)),
+            Some(max) => write!(f, "{} to {max} arguments", self.min),
+            None => write!(f, "at least {} argument{}", self.min, plural(self.min)),
+        }
+    }
+}
+
+/// A function name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::IntoStaticStr)]
+#[strum(serialize_all = "lowercase")]
 pub enum Function {
     Abs,
     Ceil,
@@ -98,44 +215,218 @@ pub enum Function {
     Lg,
     Ln,
     Exp,
+    Atan2,
+    Hypot,
+    Min,
+    Max,
+    Clamp,
+    Re,
+    Im,
+    Arg,
+    Conj,
+    Now,
+    Today,
+    Unix,
+    Popcount,
+    Clz,
+    Ctz,
+    Bswap,
+    Bitrev,
+    Parity,
+    Ilog2,
+    #[strum(serialize = "next_pow2")]
+    NextPow2,
+    #[strum(serialize = "isolate_lowest")]
+    IsolateLowest,
+    Getbits,
+    Setbits,
+    Factorial,
 }
 
 impl Function {
-    fn evaluate(&self, operand: Value) -> Value {
+    /// The number of arguments this function accepts.
+    pub fn arity(&self) -> Arity {
         match self {
-            Self::Abs => operand.abs(),
-            Self::Ceil => operand.ceil(),
-            Self::Floor => operand.floor(),
-            Self::Round => operand.round(),
-            Self::Sin => operand.sin(),
-            Self::Cos => operand.cos(),
-            Self::Tan => operand.tan(),
-            Self::Sinh => operand.sinh(),
-            Self::Cosh => operand.cosh(),
-            Self::Tanh => operand.tanh(),
-            Self::Asin => operand.asin(),
-            Self::Acos => operand.acos(),
-            Self::Atan => operand.atan(),
-            Self::Asinh => operand.asinh(),
-            Self::Acosh => operand.acosh(),
-            Self::Atanh => operand.atanh(),
-            Self::Rad => operand.rad(),
-            Self::Deg => operand.deg(),
-            Self::Sqrt => operand.sqrt(),
-            Self::Cbrt => operand.cbrt(),
-            Self::Log => operand.log(),
-            Self::Lg => operand.lg(),
-            Self::Ln => operand.ln(),
-            Self::Exp => operand.exp(),
-        }
-    }
-}
-
-/// A constant.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            Self::Log => Arity::between(1, 2),
+            Self::Atan2 | Self::Hypot => Arity::exactly(2),
+            Self::Min | Self::Max => Arity::at_least(1),
+            Self::Clamp => Arity::exactly(3),
+            Self::Now | Self::Today => Arity::exactly(0),
+            Self::Popcount | Self::Clz | Self::Ctz | Self::Bswap | Self::Bitrev | Self::Parity => {
+                Arity::between(1, 2)
+            }
+            Self::Getbits => Arity::between(2, 3),
+            Self::Setbits => Arity::exactly(4),
+            _ => Arity::exactly(1),
+        }
+    }
+
+    /// Whether this function manipulates the bits of an integer, such as `popcount`.
+    ///
+    /// Apart from `getbits` and `setbits`, those which accept a second argument take it as the width
+    /// in bits, as in `bswap(x, 32)`.
+    pub fn is_bitwise(&self) -> bool {
+        matches!(
+            self,
+            Self::Popcount
+                | Self::Clz
+                | Self::Ctz
+                | Self::Bswap
+                | Self::Bitrev
+                | Self::Parity
+                | Self::Ilog2
+                | Self::NextPow2
+                | Self::IsolateLowest
+                | Self::Getbits
+                | Self::Setbits
+        )
+    }
+
+    /// Apply this function to its arguments.
+    ///
+    /// `now()` and `today()` read the context's [clock][Context::clock].
+    fn evaluate<N: Calcable>(&self, ctx: &Context<N>, args: Vec<N>) -> Result<N> {
+        if !self.arity().accepts(args.len()) {
+            return Err(ValueError::Arity(
+                <&str>::from(self).to_owned(),
+                self.arity(),
+                args.len(),
+            ));
+        }
+        match self {
+            Self::Now => N::datetime(datetime::whole_seconds((ctx.clock)())),
+            Self::Today => N::datetime(datetime::midnight((ctx.clock)())),
+            _ => ctx.apply(args, |args| N::function(*self, args, ctx.backend)),
+        }
+    }
+}
+
+/// A built-in constant.
+///
+/// Constants are not part of the grammar: a [`Context`] binds each of them to a name when it is
+/// created, and names are resolved against the context's constants when they are evaluated.
+///
+/// Physical constants are namespaced under `phys.`, so that names such as `c` and `h`
+/// remain free for variables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::EnumIter)]
 pub enum Constant {
     E,
     Pi,
+    I,
+    Tau,
+    Phi,
+    Inf,
+    Nan,
+    Sqrt2,
+    Ln2,
+    SpeedOfLight,
+    Gravitation,
+    Planck,
+    ReducedPlanck,
+    Boltzmann,
+    Avogadro,
+    ElementaryCharge,
+    VacuumPermeability,
+    VacuumPermittivity,
+    ElectronMass,
+    ProtonMass,
+    GasConstant,
+    StefanBoltzmann,
+    FineStructure,
+}
+
+impl Constant {
+    /// The names this constant is bound to in a new [`Context`].
+    pub fn names(&self) -> &'static [&'static str] {
+        match self {
+            Self::E => &["e"],
+            Self::Pi => &["pi", "π"],
+            Self::I => &["i"],
+            Self::Tau => &["tau"],
+            Self::Phi => &["phi"],
+            Self::Inf => &["inf"],
+            Self::Nan => &["nan"],
+            Self::Sqrt2 => &["sqrt2"],
+            Self::Ln2 => &["ln2"],
+            Self::SpeedOfLight => &["phys.c"],
+            Self::Gravitation => &["phys.G"],
+            Self::Planck => &["phys.h"],
+            Self::ReducedPlanck => &["phys.hbar"],
+            Self::Boltzmann => &["phys.k_B"],
+            Self::Avogadro => &["phys.N_A"],
+            Self::ElementaryCharge => &["phys.e_charge"],
+            Self::VacuumPermeability => &["phys.mu_0"],
+            Self::VacuumPermittivity => &["phys.eps_0"],
+            Self::ElectronMass => &["phys.m_e"],
+            Self::ProtonMass => &["phys.m_p"],
+            Self::GasConstant => &["phys.R"],
+            Self::StefanBoltzmann => &["phys.sigma"],
+            Self::FineStructure => &["phys.alpha"],
+        }
+    }
+
+    /// What this constant is.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::E => "Euler's number",
+            Self::Pi => "Archimedes' constant",
+            Self::I => "imaginary unit",
+            Self::Tau => "circle constant, 2pi",
+            Self::Phi => "golden ratio",
+            Self::Inf => "positive infinity",
+            Self::Nan => "not a number",
+            Self::Sqrt2 => "square root of 2",
+            Self::Ln2 => "natural logarithm of 2",
+            Self::SpeedOfLight => "speed of light in vacuum",
+            Self::Gravitation => "Newtonian constant of gravitation",
+            Self::Planck => "Planck constant",
+            Self::ReducedPlanck => "reduced Planck constant",
+            Self::Boltzmann => "Boltzmann constant",
+            Self::Avogadro => "Avogadro constant",
+            Self::ElementaryCharge => "elementary charge",
+            Self::VacuumPermeability => "vacuum magnetic permeability",
+            Self::VacuumPermittivity => "vacuum electric permittivity",
+            Self::ElectronMass => "electron mass",
+            Self::ProtonMass => "proton mass",
+            Self::GasConstant => "molar gas constant",
+            Self::StefanBoltzmann => "Stefan-Boltzmann constant",
+            Self::FineStructure => "fine-structure constant",
+        }
+    }
+
+    /// The SI units this constant is measured in, or `None` if it is dimensionless.
+    pub fn unit(&self) -> Option<&'static str> {
+        match self {
+            Self::SpeedOfLight => Some("m s^-1"),
+            Self::Gravitation => Some("m^3 kg^-1 s^-2"),
+            Self::Planck | Self::ReducedPlanck => Some("J s"),
+            Self::Boltzmann => Some("J K^-1"),
+            Self::Avogadro => Some("mol^-1"),
+            Self::ElementaryCharge => Some("C"),
+            Self::VacuumPermeability => Some("N A^-2"),
+            Self::VacuumPermittivity => Some("F m^-1"),
+            Self::ElectronMass | Self::ProtonMass => Some("kg"),
+            Self::GasConstant => Some("J mol^-1 K^-1"),
+            Self::StefanBoltzmann => Some("W m^-2 K^-4"),
+            _ => None,
+        }
+    }
+
+    /// Where the value of this constant comes from, or `None` for mathematical constants.
+    pub fn source(&self) -> Option<&'static str> {
+        match self {
+            Self::E
+            | Self::Pi
+            | Self::I
+            | Self::Tau
+            | Self::Phi
+            | Self::Inf
+            | Self::Nan
+            | Self::Sqrt2
+            | Self::Ln2 => None,
+            _ => Some("CODATA 2022"),
+        }
+    }
 }
 
 /// What kind of history lookup is desired.
@@ -149,25 +440,154 @@ pub enum HistoryIndexKind {
 }
 
 /// A term in the expression.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// Terms borrow their text from the input, unless they have been made [owned][Expr::into_owned].
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Term<'input> {
-    Literal(&'input str),
-    HexLiteral(&'input str),
-    OctLiteral(&'input str),
-    BinLiteral(&'input str),
-    Constant(Constant),
+    Literal(Cow<'input, str>),
+    HexLiteral(Cow<'input, str>),
+    OctLiteral(Cow<'input, str>),
+    BinLiteral(Cow<'input, str>),
+    /// A date, with an optional time of day: `2026-10-17`, `2026-10-17T14:30`.
+    DateTime(Cow<'input, str>),
+    /// A duration such as `3h 20m`, or a time of day such as `T14:30`.
+    Duration(Cow<'input, str>),
+    /// A literal with a type suffix: `255u8`, `0xffu16`, `1.0f32`.
+    ///
+    /// The sign of a negative base-10 literal is part of it, so that `-128i8` is in range.
+    Typed {
+        literal: Cow<'input, str>,
+        radix: u32,
+        ty: MachineType,
+        negative: bool,
+    },
     History(HistoryIndexKind, usize),
+    Variable(Cow<'input, str>),
 }
 
 impl<'input> Term<'input> {
-    fn evaluate(&self, ctx: &Context) -> Result {
+    /// Split a literal such as `0xffu8` into its digits and its type suffix.
+    pub(crate) fn typed(literal: &'input str, radix: u32) -> Self {
+        let split = literal
+            .rfind(['u', 'i', 'f'])
+            .expect("the lexer requires a type suffix");
+        Self::Typed {
+            literal: literal[..split].into(),
+            radix,
+            ty: literal[split..]
+                .parse()
+                .expect("the lexer only accepts known types"),
+            negative: false,
+        }
+    }
+
+    /// Copy any borrowed text, so that this term no longer borrows from the input.
+    fn into_owned(self) -> Term<'static> {
+        let owned = |s: Cow<str>| Cow::Owned(s.into_owned());
         match self {
-            Self::Literal(s) => Value::parse_decimal(s).map_err(Into::into),
-            Self::HexLiteral(s) => Value::parse_hex(s).map_err(Into::into),
-            Self::OctLiteral(s) => Value::parse_octal(s).map_err(Into::into),
-            Self::BinLiteral(s) => Value::parse_binary(s).map_err(Into::into),
-            Self::Constant(Constant::E) => Ok(Value::E),
-            Self::Constant(Constant::Pi) => Ok(Value::PI),
+            Self::Literal(s) => Term::Literal(owned(s)),
+            Self::HexLiteral(s) => Term::HexLiteral(owned(s)),
+            Self::OctLiteral(s) => Term::OctLiteral(owned(s)),
+            Self::BinLiteral(s) => Term::BinLiteral(owned(s)),
+            Self::DateTime(s) => Term::DateTime(owned(s)),
+            Self::Duration(s) => Term::Duration(owned(s)),
+            Self::Typed {
+                literal,
+                radix,
+                ty,
+                negative,
+            } => Term::Typed {
+                literal: owned(literal),
+                radix,
+                ty,
+                negative,
+            },
+            Self::History(kind, idx) => Term::History(kind, idx),
+            Self::Variable(s) => Term::Variable(owned(s)),
+        }
+    }
+
+    /// The subtraction which a date such as `2000-10-10` would be without dates.
+    fn date_difference(date: &str) -> Option<Expr<'_>> {
+        if !date.bytes().all(|b| b.is_ascii_digit() || b == b'-') {
+            return None;
+        }
+        let mut parts = date
+            .split('-')
+            .map(|part| Expr::Term(Term::Literal(part.into())));
+        let year = parts.next()?;
+        Some(parts.fold(year, |difference, part| {
+            Expr::Infix(Box::new(difference), InfixOperator::Sub, Box::new(part))
+        }))
+    }
+
+    /// The product which a single-unit duration such as `3m` would be without durations: `3 * m`.
+    fn duration_product(duration: &str) -> Option<(&str, Expr<'_>)> {
+        if duration.contains([' ', ':']) {
+            return None;
+        }
+        let (number, name) = duration.split_at(duration.find(char::is_alphabetic)?);
+        let product = Expr::Infix(
+            Box::new(Expr::Term(Term::Literal(number.into()))),
+            InfixOperator::Mul,
+            Box::new(Expr::Term(Term::Variable(name.into()))),
+        );
+        Some((name, product))
+    }
+
+    fn evaluate<N: Calcable>(&self, ctx: &Context<N>, scope: &Scope<N>) -> Result<N> {
+        match self {
+            Self::Literal(s) => N::parse(s, 10, ctx.backend),
+            Self::HexLiteral(s) => N::parse(s, 16, ctx.backend),
+            Self::OctLiteral(s) => N::parse(s, 8, ctx.backend),
+            Self::BinLiteral(s) => N::parse(s, 2, ctx.backend),
+            Self::DateTime(s) => match Self::date_difference(s) {
+                // without dates, `2000-10-10` is the subtraction it looks like
+                Some(difference)
+                    if matches!(N::datetime(UNIX_EPOCH), Err(ValueError::Unsupported(_))) =>
+                {
+                    difference.evaluate_in(ctx, scope)
+                }
+                _ => datetime::parse_datetime(s)
+                    .ok_or_else(|| ValueError::InvalidDate(s.to_string()))
+                    .and_then(N::datetime),
+            },
+            Self::Duration(s) => match Self::duration_product(s) {
+                // `3m` is a multiplication when `m` is a variable, as it would be without durations
+                Some((name, product)) if binding(name, ctx, scope).is_some() => {
+                    product.evaluate_in(ctx, scope)
+                }
+                _ => datetime::parse_duration(s)
+                    .ok_or_else(|| ValueError::InvalidDate(s.to_string()))
+                    .and_then(N::duration),
+            },
+            Self::Typed {
+                literal,
+                radix,
+                ty,
+                negative,
+            } => {
+                // the suffix decides how the literal is represented, whatever the backend
+                let backend = Backend::default();
+                let mut value = N::parse(literal, *radix, backend)?;
+                if *negative {
+                    value = N::prefix(PrefixOperator::Negation, value, backend)?;
+                }
+                if ty.is_float() {
+                    return value.cast(*ty);
+                }
+                // literals in other bases are bit patterns, so `0xffi8` is `-1`
+                let range = if *radix == 10 { *ty } else { ty.unsigned() };
+                let cast = value.clone().cast(range)?;
+                if cast != value {
+                    let sign = if *negative { "-" } else { "" };
+                    return Err(ValueError::LiteralRange(
+                        format!("{sign}{literal}{ty}"),
+                        *ty,
+                    ));
+                }
+                cast.cast(*ty)
+            }
             Self::History(kind, idx) => {
                 let err = || ValueError::HistoryOOB(*kind, *idx, ctx.history.len());
                 let real_idx = match kind {
@@ -178,6 +598,8 @@ impl<'input> Term<'input> {
                 };
                 ctx.history.get(real_idx).cloned().ok_or_else(err)
             }
+            Self::Variable(name) => lookup(name, ctx, scope)
+                .ok_or_else(|| ValueError::UnknownVariable(name.to_string())),
         }
     }
 }
@@ -188,25 +610,346 @@ pub enum Expr<'input> {
     Term(Term<'input>),
     Prefix(PrefixOperator, Box<Expr<'input>>),
     Infix(Box<Expr<'input>>, InfixOperator, Box<Expr<'input>>),
-    Func(Function, Box<Expr<'input>>),
+    Func(Function, Vec<Expr<'input>>),
+    Call(Cow<'input, str>, Vec<Expr<'input>>),
     Group(Box<Expr<'input>>),
+    Conditional(Box<Expr<'input>>, Box<Expr<'input>>, Box<Expr<'input>>),
+    /// A conversion to a machine type: `x as u8`.
+    Cast(Box<Expr<'input>>, MachineType),
 }
 
 impl<'input> Expr<'input> {
+    /// Negate an expression.
+    ///
+    /// The sign of a base-10 typed literal becomes part of it, so that `-128i8` is in range.
+    pub(crate) fn negation(expr: Self) -> Self {
+        match expr {
+            Self::Term(Term::Typed {
+                literal,
+                radix: 10,
+                ty,
+                negative,
+            }) => Self::Term(Term::Typed {
+                literal,
+                radix: 10,
+                ty,
+                negative: !negative,
+            }),
+            expr => Self::Prefix(PrefixOperator::Negation, Box::new(expr)),
+        }
+    }
+
+    /// Copy any borrowed text, so that this expression no longer borrows from the input.
+    pub fn into_owned(self) -> Expr<'static> {
+        let owned = |expr: Box<Self>| Box::new(expr.into_owned());
+        let all = |exprs: Vec<Self>| exprs.into_iter().map(Self::into_owned).collect();
+        match self {
+            Self::Term(term) => Expr::Term(term.into_owned()),
+            Self::Prefix(prefix, expr) => Expr::Prefix(prefix, owned(expr)),
+            Self::Infix(left, infix, right) => Expr::Infix(owned(left), infix, owned(right)),
+            Self::Func(func, args) => Expr::Func(func, all(args)),
+            Self::Call(name, args) => Expr::Call(Cow::Owned(name.into_owned()), all(args)),
+            Self::Group(expr) => Expr::Group(owned(expr)),
+            Self::Conditional(condition, then, otherwise) => {
+                Expr::Conditional(owned(condition), owned(then), owned(otherwise))
+            }
+            Self::Cast(expr, ty) => Expr::Cast(owned(expr), ty),
+        }
+    }
+
+    /// The machine type which typed literals and casts give the result of this expression, if any.
+    ///
+    /// As in Rust, an arithmetic or bitwise operator has the type of its operands, and an untyped
+    /// operand takes the type of the other: `255u8 + 1` is a `u8`. The type of a power, shift,
+    /// or rotation is that of its left operand. Operands of two different types are an error.
+    ///
+    /// Only the expression is typed: a variable holds a number, not a type.
+    pub fn machine_type(&self) -> Result<Option<MachineType>> {
+        match self {
+            Self::Term(Term::Typed { ty, .. }) | Self::Cast(_, ty) => Ok(Some(*ty)),
+            Self::Prefix(_, expr) | Self::Group(expr) => expr.machine_type(),
+            Self::Infix(left, infix, right) if infix.is_arithmetic() => {
+                let left = left.machine_type()?;
+                if infix.is_untyped_right() {
+                    return Ok(left);
+                }
+                match (left, right.machine_type()?) {
+                    (Some(left), Some(right)) if left != right => {
+                        Err(ValueError::TypeMismatch(left, right))
+                    }
+                    (left, right) => Ok(left.or(right)),
+                }
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// The backend in which to apply the operator at the root of this expression.
+    ///
+    /// A typed operator is pinned to its type, with the context's overflow behavior if it has one.
+    fn backend<N>(&self, ctx: &Context<N>) -> Result<Backend> {
+        Ok(match self.machine_type()? {
+            Some(ty) => Backend::Machine {
+                ty,
+                overflow: ctx
+                    .backend
+                    .machine()
+                    .map(|machine| machine.overflow)
+                    .unwrap_or_default(),
+            },
+            None => ctx.backend,
+        })
+    }
+
     /// Evaluate this expression into its mathematical result.
-    pub(crate) fn evaluate(&self, ctx: &Context) -> Result {
+    pub fn evaluate<N: Calcable>(&self, ctx: &Context<N>) -> Result<N> {
+        self.evaluate_in(ctx, &Scope::default())
+    }
+
+    /// Evaluate this expression with some local bindings in scope.
+    fn evaluate_in<N: Calcable>(&self, ctx: &Context<N>, scope: &Scope<N>) -> Result<N> {
         match self {
-            Self::Term(term) => term.evaluate(ctx),
-            Self::Prefix(prefix, expr) => prefix.evaluate(expr.evaluate(ctx)?),
+            Self::Term(term) => term.evaluate(ctx, scope),
+            Self::Prefix(prefix, expr) => {
+                let backend = self.backend(ctx)?;
+                ctx.apply([expr.evaluate_in(ctx, scope)?], |[operand]| {
+                    prefix.evaluate(operand, backend)
+                })
+            }
             Self::Infix(left, infix, right) => {
-                infix.evaluate(left.evaluate(ctx)?, right.evaluate(ctx)?)
+                let backend = self.backend(ctx)?;
+                let left = left.evaluate_in(ctx, scope)?;
+                match infix.short_circuit(&left) {
+                    Some(result) => Ok(result),
+                    None => ctx.apply([left, right.evaluate_in(ctx, scope)?], |[left, right]| {
+                        infix.evaluate(left, right, backend)
+                    }),
+                }
+            }
+            Self::Func(func, args) => func.evaluate(ctx, evaluate_all(args, ctx, scope)?),
+            Self::Call(name, args) => {
+                if let Some(function) = ctx.native_functions.get(name.as_ref()) {
+                    let args = evaluate_all(args, ctx, scope)?;
+                    return ctx.apply(args, |args| function.call(name, args));
+                }
+                let Some(function) = ctx.functions.get(name.as_ref()) else {
+                    // `a(b)` where `a` is a variable is an implicit multiplication
+                    return match (lookup(name, ctx, scope), args.as_slice()) {
+                        (Some(value), [arg]) => {
+                            let arg = arg.evaluate_in(ctx, scope)?;
+                            ctx.apply([value, arg], |[value, arg]| {
+                                N::infix(InfixOperator::Mul, value, arg, ctx.backend)
+                            })
+                        }
+                        _ => Err(ValueError::UnknownFunction(name.to_string())),
+                    };
+                };
+                if args.len() != function.params.len() {
+                    return Err(ValueError::Arity(
+                        name.to_string(),
+                        Arity::exactly(function.params.len()),
+                        args.len(),
+                    ));
+                }
+                function.call(ctx, evaluate_all(args, ctx, scope)?, scope.depth + 1)
+            }
+            Self::Group(expr) => expr.evaluate_in(ctx, scope),
+            Self::Conditional(condition, then, otherwise) => {
+                if condition.evaluate_in(ctx, scope)?.is_truthy() {
+                    then.evaluate_in(ctx, scope)
+                } else {
+                    otherwise.evaluate_in(ctx, scope)
+                }
+            }
+            Self::Cast(expr, ty) => {
+                ctx.apply([expr.evaluate_in(ctx, scope)?], |[value]| value.cast(*ty))
+            }
+        }
+    }
+}
+
+/// Find the value of a name: locals shadow the context's constants and variables,
+/// which in turn shadow units.
+fn lookup<N: Calcable>(name: &str, ctx: &Context<N>, scope: &Scope<N>) -> Option<N> {
+    binding(name, ctx, scope).or_else(|| N::unit(name))
+}
+
+/// Find the value of a name which is bound as a local, constant, or variable, but not a unit.
+fn binding<N: Calcable>(name: &str, ctx: &Context<N>, scope: &Scope<N>) -> Option<N> {
+    scope
+        .get(name)
+        .or_else(|| ctx.constants.get(name).cloned())
+        .or_else(|| ctx.variables.get(name).cloned())
+}
+
+/// Evaluate each of a list of arguments in order.
+fn evaluate_all<N: Calcable>(args: &[Expr], ctx: &Context<N>, scope: &Scope<N>) -> Result<Vec<N>> {
+    args.iter().map(|arg| arg.evaluate_in(ctx, scope)).collect()
+}
+
+/// Local bindings visible while evaluating an expression.
+///
+/// At the top level this is empty; within a user-defined function it holds the arguments.
+#[derive(Debug)]
+struct Scope<'a, N> {
+    locals: Vec<(&'a str, N)>,
+    depth: usize,
+}
+
+impl<N> Default for Scope<'_, N> {
+    fn default() -> Self {
+        Self {
+            locals: Vec::new(),
+            depth: 0,
+        }
+    }
+}
+
+impl<N: Clone> Scope<'_, N> {
+    fn get(&self, name: &str) -> Option<N> {
+        self.locals
+            .iter()
+            .find_map(|(local, value)| (*local == name).then(|| value.clone()))
+    }
+}
+
+/// A function definition such as `f(x, y) = x**2 + y`, as parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Definition<'input> {
+    pub name: &'input str,
+    pub params: Vec<&'input str>,
+    pub body: Expr<'input>,
+    /// Byte offsets of the body within the source text.
+    pub body_span: (usize, usize),
+}
+
+/// A function defined at runtime.
+///
+/// The body is retained both as source text, to display, and as an owned expression, to call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserFunction {
+    name: String,
+    params: Vec<String>,
+    body: String,
+    expr: Expr<'static>,
+}
+
+impl UserFunction {
+    /// Create a function from a parsed definition and the source text it was parsed from.
+    pub(crate) fn new(definition: &Definition, src: &str) -> Self {
+        let (start, end) = definition.body_span;
+        Self {
+            name: definition.name.to_owned(),
+            params: definition.params.iter().map(|&p| p.to_owned()).collect(),
+            body: src[start..end].trim().to_owned(),
+            expr: definition.body.clone().into_owned(),
+        }
+    }
+
+    /// The name of this function.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The names of this function's parameters.
+    pub fn params(&self) -> &[String] {
+        &self.params
+    }
+
+    /// The source text of this function's body.
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    fn call<N: Calcable>(&self, ctx: &Context<N>, args: Vec<N>, depth: usize) -> Result<N> {
+        if depth > MAX_CALL_DEPTH {
+            return Err(ValueError::RecursionLimit(MAX_CALL_DEPTH));
+        }
+        let scope = Scope {
+            locals: self.params.iter().map(String::as_str).zip(args).collect(),
+            depth,
+        };
+        self.expr.evaluate_in(ctx, &scope)
+    }
+}
+
+/// A function implemented in Rust, registered with [`Context::register_fn`].
+pub struct NativeFunction<N = Value> {
+    arity: Arity,
+    function: Box<dyn Fn(Vec<N>) -> Result<N>>,
+}
+
+impl<N> NativeFunction<N> {
+    /// Create a function which accepts `arity` arguments.
+    pub fn new(arity: impl Into<Arity>, function: impl Fn(Vec<N>) -> Result<N> + 'static) -> Self {
+        Self {
+            arity: arity.into(),
+            function: Box::new(function),
+        }
+    }
+
+    /// The number of arguments this function accepts.
+    pub fn arity(&self) -> Arity {
+        self.arity
+    }
+
+    fn call(&self, name: &str, args: Vec<N>) -> Result<N> {
+        if !self.arity.accepts(args.len()) {
+            return Err(ValueError::Arity(name.to_owned(), self.arity, args.len()));
+        }
+        (self.function)(args)
+    }
+}
+
+impl<N> fmt::Debug for NativeFunction<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NativeFunction")
+            .field("arity", &self.arity)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A complete statement: an expression, optionally bound to a variable name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Statement<'input> {
+    Expr(Expr<'input>),
+    Assign(&'input str, Expr<'input>),
+}
+
+impl<'input> Statement<'input> {
+    /// The variable name this statement assigns to, if any.
+    pub fn target(&self) -> Option<&'input str> {
+        match self {
+            Self::Expr(_) => None,
+            Self::Assign(name, _) => Some(name),
+        }
+    }
+
+    /// Evaluate the expression of this statement into its mathematical result.
+    ///
+    /// This does not perform the assignment; that is up to the caller.
+    /// The result is adjusted as the context's [`Backend`][crate::Backend] requires.
+    pub fn evaluate<N: Calcable>(&self, ctx: &Context<N>) -> Result<N> {
+        match self {
+            Self::Expr(expr) | Self::Assign(_, expr) => {
+                expr.evaluate(ctx).map(|value| value.finish(ctx.backend))
             }
-            Self::Func(func, expr) => Ok(func.evaluate(expr.evaluate(ctx)?)),
-            Self::Group(expr) => expr.evaluate(ctx),
         }
     }
 }
 
+impl fmt::Display for UserFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}({}) = {}",
+            self.name,
+            self.params.join(", "),
+            self.body
+        )
+    }
+}
+
 /// Error produced by [`AnnotatedExpr`].
 #[derive(Debug, thiserror::Error)]
 pub enum AnnotatedError {
@@ -216,23 +959,61 @@ pub enum AnnotatedError {
     Format(#[from] num_runtime_fmt::Error),
 }
 
-/// An expression annotated with some metadata.
+/// A conversion of a result to a unit, such as `to km/h`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conversion<'input> {
+    /// The unit, which is evaluated like any other expression.
+    pub unit: Expr<'input>,
+    /// The unit as it is displayed after the converted result.
+    pub name: String,
+}
+
+/// A statement annotated with some metadata.
 pub struct AnnotatedExpr<'input> {
-    pub expr: Expr<'input>,
+    pub stmt: Statement<'input>,
+    /// The unit to render the result in, if any.
+    pub conversion: Option<Conversion<'input>>,
     pub format: NumFmt,
+    /// Whether to render the result in polar form.
+    pub polar: bool,
 }
 
 impl<'input> AnnotatedExpr<'input> {
     /// Evaluate this expression into its mathematical result.
     ///
     /// Return the result as a bare type and also formatted according to the
-    /// requested format string.
-    pub fn evaluate(&self, ctx: &Context) -> Result<(Value, String), AnnotatedError> {
+    /// requested format string. A conversion applies only to the formatted result.
+    pub fn evaluate<N: Calcable>(&self, ctx: &Context<N>) -> Result<(N, String), AnnotatedError> {
         let value = self
-            .expr
+            .stmt
             .evaluate(ctx)
             .map_err(AnnotatedError::Calculation)?;
-        let formatted = self.format.fmt(value)?;
+        let shown = match &self.conversion {
+            Some(conversion) => conversion
+                .unit
+                .evaluate(ctx)
+                .and_then(|unit| value.clone().convert(unit))
+                .map_err(AnnotatedError::Calculation)?
+                .finish(ctx.backend),
+            None => value.clone(),
+        };
+        // the context's type wins, as the result has been fitted to it
+        let ty = match ctx.backend.machine() {
+            Some(machine) => Some(machine.ty),
+            None => match &self.stmt {
+                Statement::Expr(expr) | Statement::Assign(_, expr) => {
+                    expr.machine_type().map_err(AnnotatedError::Calculation)?
+                }
+            },
+        };
+        let mut formatted = match ty {
+            _ if self.polar => shown.format_polar(&self.format)?,
+            Some(ty) => shown.format_as(&self.format, ty)?,
+            None => shown.format(&self.format)?,
+        };
+        if let Some(conversion) = &self.conversion {
+            formatted = format!("{formatted} {}", conversion.name);
+        }
         Ok((value, formatted))
     }
 }
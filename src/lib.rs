@@ -7,28 +7,177 @@
 //! - Parse an [`ast::Expr`] with [`ast::parser::ExprParser`].
 //! - Evaluate that expression with [`Context::evaluate`].
 //!
+//! [`Context::evaluate`] also accepts assignments such as `x = 3 * pi` or `let r = @ / 2`;
+//! the assigned names are then available to subsequent expressions in the same context.
+//! Functions such as `f(x, y) = x**2 + y` can be defined with [`Context::define`],
+//! and functions implemented in Rust can be registered with [`Context::register_fn`].
+//! Constants such as `pi` and `tau` are bound in every new context; more can be added
+//! with [`Context::define_constant`] or loaded from a file with [`Context::load_constants`].
+//!
 //! You can freely modify the parsed expression; the types in [`ast`] are all public.
 //!
-//! To enable calculation based on your custom numeric type, just impl [`types::Calcable`] for your type.
+//! To calculate with units of measure, such as `5 kW * 3 h`, or with dates and durations,
+//! such as `2026-12-25 - today()`, evaluate expressions in a `Context::<Quantity>::new()`.
+//!
+//! To enable calculation based on your custom numeric type, just impl [`types::Calcable`] for your type,
+//! and evaluate expressions in a `Context::<YourType>::new()`.
 
 pub mod ast;
+pub mod types;
 mod value;
 
+use std::{cell::Cell, collections::HashMap, time::SystemTime};
+
 use ast::{
-    parser::{AnnotatedExprParser, ExprParser},
-    AnnotatedError, ParseError as UserParseError,
+    parser::{AnnotatedExprParser, DefinitionParser, ExprParser, StatementParser},
+    AnnotatedError, Arity, Constant, Definition, Expr, NativeFunction,
+    ParseError as UserParseError, Term, UserFunction,
 };
 use lalrpop_util::ParseError;
+use strum::IntoEnumIterator;
+use types::Calcable;
 pub(crate) use value::Result;
-pub use value::{ArithmeticError, Error as ValueError, ParseValueError, Value};
+pub use value::{
+    ArithmeticError, Dimension, Error as ValueError, MachineType, Overflow, ParseDimensionError,
+    ParseValueError, Quantity, Value, MAX_ARBITRARY_BITS,
+};
 
 /// Calculation context.
 ///
 /// Stores a history of calculated values, so that the history lookups (`@`) work properly.
-/// Also reifies the numeric type backing the calculations.
-#[derive(Default)]
-pub struct Context {
-    pub history: Vec<Value>,
+/// Also stores named variables and user-defined functions, so that they persist between expressions,
+/// and the named constants which expressions can refer to.
+///
+/// Expressions are evaluated in the numeric type `N`, which is [`Value`] unless otherwise specified.
+pub struct Context<N = Value> {
+    pub history: Vec<N>,
+    pub variables: HashMap<String, N>,
+    pub constants: HashMap<String, N>,
+    pub functions: HashMap<String, UserFunction>,
+    pub native_functions: HashMap<String, NativeFunction<N>>,
+    pub backend: Backend,
+    /// What happens when an operation rounds an exact number, such as a 64-bit counter, to a float.
+    pub precision: Precision,
+    /// The source of the current time for `now()` and `today()`.
+    ///
+    /// This is the system clock by default; replace it to evaluate deterministically.
+    pub clock: Box<dyn Fn() -> SystemTime>,
+    /// Whether the most recently evaluated expression lost precision.
+    ///
+    /// This is only tracked under [`Precision::Warn`]; otherwise it is always false.
+    pub inexact: Cell<bool>,
+}
+
+impl<N: Calcable> Context<N> {
+    /// Create an empty context which evaluates expressions in the numeric type `N`.
+    ///
+    /// Each built-in [`Constant`] which `N` supports is bound to its names.
+    /// For the default numeric type [`Value`], `Context::default()` is equivalent.
+    pub fn new() -> Self {
+        let constants = Constant::iter()
+            .filter_map(|constant| Some((constant.names(), N::constant(constant).ok()?)))
+            .flat_map(|(names, value)| {
+                names
+                    .iter()
+                    .map(move |&name| (name.to_owned(), value.clone()))
+            })
+            .collect();
+        Self {
+            history: Vec::new(),
+            variables: HashMap::new(),
+            constants,
+            functions: HashMap::new(),
+            native_functions: HashMap::new(),
+            backend: Backend::default(),
+            precision: Precision::default(),
+            clock: Box::new(SystemTime::now),
+            inexact: Cell::new(false),
+        }
+    }
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// How a [`Context`] represents numbers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// Non-integral literals such as `0.1` are binary floating-point numbers.
+    #[default]
+    Float,
+    /// Non-integral literals are base-10 decimals, so that `0.1 + 0.2 == 0.3`.
+    ///
+    /// Decimal results are rounded to `scale` digits after the decimal point, halfway cases
+    /// away from zero.
+    Decimal { scale: u32 },
+    /// As [`Backend::Float`], but an operation on finite real numbers which has no finite real
+    /// result is an error, rather than an infinity, NaN, or a complex number.
+    ///
+    /// So `sqrt(-1)` and `ln(0)` are [`ValueError::Domain`] errors, `1.0 / 0` is
+    /// [`ArithmeticError::DivideBy0`], and `1e308 * 10` is [`ArithmeticError::Overflow`].
+    /// Operations with complex or non-finite operands, such as `i**2` or `inf - 1`, are unchanged.
+    StrictFloat,
+    /// All arithmetic is pinned to one fixed-width type, as on the hardware which has it:
+    /// on a `u8`, `!0x0f` is `0xf0` and `200 + 100` is `44`.
+    ///
+    /// Integer results which do not fit the type are handled according to `overflow`.
+    /// Literals in bases other than 10 are bit patterns, so on an `i8`, `0xff` is `-1`.
+    Machine { ty: MachineType, overflow: Overflow },
+}
+
+impl Backend {
+    /// The machine type to which this backend pins arithmetic, if any.
+    pub(crate) fn machine(self) -> Option<value::Machine> {
+        match self {
+            Backend::Machine { ty, overflow } => Some(value::Machine { ty, overflow }),
+            _ => None,
+        }
+    }
+
+    /// Round a result according to this backend.
+    ///
+    /// For a machine type, a value which the type cannot represent is left unchanged.
+    /// For decimals, fractions are rounded too, unless they are beyond the range of a decimal.
+    pub(crate) fn round(self, value: Value) -> Value {
+        if let Some(machine) = self.machine() {
+            return machine.coerce(&value).unwrap_or(value);
+        }
+        let Backend::Decimal { scale } = self else {
+            return value;
+        };
+        let decimal = match value {
+            Value::Decimal(n) => n,
+            // so that `10/3` is rounded like `10.00/3`
+            Value::Rational(ref n) => match value::rational_to_decimal(n) {
+                Some(n) => n,
+                None => return value,
+            },
+            value => return value,
+        };
+        Value::Decimal(
+            decimal.round_dp_with_strategy(
+                scale,
+                rust_decimal::RoundingStrategy::MidpointAwayFromZero,
+            ),
+        )
+    }
+}
+
+/// What a [`Context`] does when an operation loses precision, such as when `2**53 + 1`
+/// is combined with a float and rounded to `2**53`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum Precision {
+    /// Round silently.
+    #[default]
+    Allow,
+    /// Round, but record it in [`Context::inexact`].
+    Warn,
+    /// Fail with [`ValueError::Inexact`].
+    Error,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -39,6 +188,12 @@ pub enum Error {
     Eval(#[from] ValueError),
     #[error("Formatting")]
     Format(#[source] num_runtime_fmt::Error),
+    #[error("\"{0}\" is reserved or is not a valid name")]
+    InvalidName(String),
+    #[error("\"{0}\" is already defined as a {1}")]
+    NameConflict(String, &'static str),
+    #[error("line {0} is not of the form `name = expr`")]
+    ConstantSyntax(usize),
 }
 
 impl From<AnnotatedError> for Error {
@@ -50,31 +205,178 @@ impl From<AnnotatedError> for Error {
     }
 }
 
-impl Context {
+impl<N: Calcable> Context<N> {
     /// Evaluate an expression in this context.
     ///
     /// This both returns the calculated value and stores a copy in the context's history.
-    pub fn evaluate(&mut self, expr: &str) -> Result<Value, Error> {
-        let parser = ExprParser::new();
-        let expr = parser.parse(expr).map_err(|err| err.map_token(|_| ""))?;
-        let result = expr.evaluate(self).map_err(Error::Eval)?;
-        self.history.push(result);
+    /// If the expression is an assignment, the value is also bound to the assigned name.
+    /// Assigning to the name of a constant is an error.
+    pub fn evaluate(&mut self, expr: &str) -> Result<N, Error> {
+        self.inexact.set(false);
+        let parser = StatementParser::new();
+        let stmt = parser.parse(expr).map_err(|err| err.map_token(|_| ""))?;
+        let result = stmt.evaluate(self).map_err(Error::Eval)?;
+        self.store(stmt.target(), result.clone())?;
+        Ok(result)
+    }
+
+    /// Define a function in this context, such as `f(x, y) = x**2 + y`.
+    ///
+    /// Any existing function of the same name is replaced; the name of a constant is an error.
+    /// Names in the body which are not parameters are resolved when the function is called.
+    pub fn define(&mut self, def: &str) -> Result<&UserFunction, Error> {
+        let parser = DefinitionParser::new();
+        let definition = parser.parse(def).map_err(|err| err.map_token(|_| ""))?;
+        self.insert_function(&definition, def)
+    }
+
+    /// Register a function implemented in Rust, such as `pages(bytes)`, under `name`.
+    ///
+    /// `arity` is the number of arguments it accepts: either a plain number or an [`Arity`].
+    /// Any existing function of the same name is replaced. Names which are reserved,
+    /// such as those of the built-in functions, are an error, as are the names of constants.
+    pub fn register_fn(
+        &mut self,
+        name: &str,
+        arity: impl Into<Arity>,
+        function: impl Fn(Vec<N>) -> Result<N, ValueError> + 'static,
+    ) -> Result<(), Error> {
+        if !is_valid_name(name) {
+            return Err(Error::InvalidName(name.to_owned()));
+        }
+        self.ensure_not_constant(name)?;
+        self.functions.remove(name);
+        self.native_functions
+            .insert(name.to_owned(), NativeFunction::new(arity, function));
+        Ok(())
+    }
+
+    /// Bind `value` to `name` as a constant, such as `c = 299792458`.
+    ///
+    /// Any existing constant of the same name is replaced. Names which are reserved are an error,
+    /// as are the names of functions and variables: a constant may not shadow them.
+    pub fn define_constant(&mut self, name: &str, value: N) -> Result<(), Error> {
+        if !is_valid_name(name) {
+            return Err(Error::InvalidName(name.to_owned()));
+        }
+        if self.functions.contains_key(name) || self.native_functions.contains_key(name) {
+            return Err(Error::NameConflict(name.to_owned(), "function"));
+        }
+        if self.variables.contains_key(name) {
+            return Err(Error::NameConflict(name.to_owned(), "variable"));
+        }
+        self.constants.insert(name.to_owned(), value);
+        Ok(())
+    }
+
+    /// Define constants from the contents of a configuration file.
+    ///
+    /// Each line has the form `name = expr`, and is defined as by [`Context::define_constant`].
+    /// Expressions may refer to constants defined on earlier lines.
+    /// Blank lines, and lines beginning with `#`, are ignored.
+    pub fn load_constants(&mut self, src: &str) -> Result<(), Error> {
+        for (number, line) in src.lines().map(str::trim).enumerate() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((name, expr)) = line.split_once('=') else {
+                return Err(Error::ConstantSyntax(number + 1));
+            };
+            let expr = ExprParser::new()
+                .parse(expr)
+                .map_err(|err| err.map_token(|_| ""))?;
+            let value = expr.evaluate(self)?.finish(self.backend);
+            self.define_constant(name.trim(), value)?;
+        }
+        Ok(())
+    }
+
+    fn insert_function(
+        &mut self,
+        definition: &Definition,
+        src: &str,
+    ) -> Result<&UserFunction, Error> {
+        let name = definition.name.to_owned();
+        self.ensure_not_constant(&name)?;
+        self.native_functions.remove(&name);
+        self.functions
+            .insert(name.clone(), UserFunction::new(definition, src));
+        Ok(&self.functions[&name])
+    }
+
+    /// Constants may not be redefined as variables or functions.
+    fn ensure_not_constant(&self, name: &str) -> Result<(), Error> {
+        if self.constants.contains_key(name) {
+            return Err(Error::NameConflict(name.to_owned(), "constant"));
+        }
+        Ok(())
+    }
+
+    /// Apply an operation to `operands`, checking whether it loses precision according to
+    /// [`Context::precision`].
+    ///
+    /// `operands` are only copied when the check is needed.
+    pub(crate) fn apply<A>(
+        &self,
+        operands: A,
+        operation: impl FnOnce(A) -> Result<N, ValueError>,
+    ) -> Result<N, ValueError>
+    where
+        A: AsRef<[N]> + Clone,
+    {
+        if self.precision == Precision::Allow {
+            return operation(operands);
+        }
+        let result = operation(operands.clone())?;
+        if N::lost_precision(operands.as_ref(), &result) {
+            if self.precision == Precision::Error {
+                return Err(ValueError::Inexact);
+            }
+            self.inexact.set(true);
+        }
         Ok(result)
     }
+
+    /// Record a result in the history, binding it to `target` if present.
+    fn store(&mut self, target: Option<&str>, result: N) -> Result<(), Error> {
+        if let Some(name) = target {
+            self.ensure_not_constant(name)?;
+            self.variables.insert(name.to_owned(), result.clone());
+        }
+        self.history.push(result);
+        Ok(())
+    }
+}
+
+/// Whether `name` can be bound by the user: it must parse as a plain variable,
+/// not as a keyword or a built-in function.
+fn is_valid_name(name: &str) -> bool {
+    matches!(
+        ExprParser::new().parse(name),
+        Ok(Expr::Term(Term::Variable(parsed))) if parsed == name
+    )
 }
 
-impl Context {
+impl<N: Calcable> Context<N> {
     /// Evaluate an annotated expression in this context.
     ///
     /// Annotations can include output formatting directives. Therefore, the return value
     /// is a formatted `String`.
     ///
-    /// This also stores a copy in the context's history.
+    /// This also stores a copy in the context's history, and performs any assignment.
+    ///
+    /// If the input is a function definition, the function is defined as by [`Context::define`]
+    /// and the normalized definition is returned instead. The history is unchanged.
     pub fn evaluate_annotated(&mut self, expr: &str) -> Result<String, Error> {
+        self.inexact.set(false);
+        if let Ok(definition) = DefinitionParser::new().parse(expr) {
+            return Ok(self.insert_function(&definition, expr)?.to_string());
+        }
+
         let parser = AnnotatedExprParser::new();
         let expr = parser.parse(expr).map_err(|err| err.map_token(|_| ""))?;
         let (result, formatted) = expr.evaluate(self)?;
-        self.history.push(result);
+        self.store(expr.stmt.target(), result)?;
         Ok(formatted)
     }
 }
@@ -1,6 +1,11 @@
+use std::{collections::HashSet, path::PathBuf};
+
 use anyhow::{bail, Context as _, Result};
-use calc::{Context, Error};
+use calc::{
+    ast::Constant, Backend, Context, Error, MachineType, Overflow, Precision, Quantity, Value,
+};
 use clap::Parser;
+use strum::IntoEnumIterator;
 
 const BIN_NAME: &str = env!("CARGO_BIN_NAME");
 const PKG_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -34,19 +39,149 @@ struct Opt {
     /// Emit version and build information.
     #[arg(short = 'V', long)]
     version: bool,
+
+    /// Parse non-integral numbers as exact base-10 decimals, rounding results to SCALE decimal places.
+    #[arg(
+        short,
+        long,
+        value_name = "SCALE",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "28"
+    )]
+    decimal: Option<u32>,
+
+    /// Report operations on finite real numbers which have no finite real result, such as `sqrt(-1)`
+    /// or `ln(0)`, as errors rather than infinities, NaN, or complex numbers.
+    #[arg(long, conflicts_with_all = ["decimal", "ty"])]
+    strict: bool,
+
+    /// Pin all arithmetic to one machine type: u8, u16, u32, u64, u128, i8 .. i128, f32, or f64.
+    #[arg(short, long = "type", value_name = "TYPE", conflicts_with = "decimal")]
+    ty: Option<MachineType>,
+
+    /// What happens when integer arithmetic overflows the machine type: wrap, saturate, or error.
+    #[arg(long, value_name = "BEHAVIOR", default_value_t, requires = "ty")]
+    overflow: Overflow,
+
+    /// What happens when an exact number is rounded to a float: allow it, warn by marking the
+    /// result as inexact, or fail with an error.
+    #[arg(long, value_name = "POLICY", default_value_t = Precision::Warn)]
+    precision: Precision,
+
+    /// Load constants from FILE, in which each line has the form `name = expr`.
+    #[arg(short, long, value_name = "FILE")]
+    constants: Vec<PathBuf>,
 }
 
 impl Opt {
     fn expr(&self) -> String {
         self.expression.join(" ")
     }
+
+    fn context(&self) -> Result<Context<Quantity>> {
+        let backend = match (self.decimal, self.ty) {
+            (Some(scale), _) => Backend::Decimal { scale },
+            (None, Some(ty)) => Backend::Machine {
+                ty,
+                overflow: self.overflow,
+            },
+            (None, None) if self.strict => Backend::StrictFloat,
+            (None, None) => Backend::Float,
+        };
+        let mut ctx = Context::new();
+        ctx.backend = backend;
+        ctx.precision = self.precision;
+        for path in &self.constants {
+            let src = std::fs::read_to_string(path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            ctx.load_constants(&src)
+                .with_context(|| format!("failed to load constants from {}", path.display()))?;
+        }
+        Ok(ctx)
+    }
+}
+
+/// Render a value for the constants table, using scientific notation for very large or small floats.
+fn scientific(value: &Value) -> String {
+    match value {
+        Value::Float(n) if n.is_normal() && !(1e-3..1e9).contains(&n.abs()) => format!("{n:e}"),
+        value => value.to_string(),
+    }
 }
 
-fn eval_and_print(ctx: &mut Context, expr: &str) -> Result<()> {
+/// Tabulate the constants of `ctx`: the built-in constants first, then any others by name.
+fn constants_table(ctx: &Context<Quantity>) -> String {
+    let mut rows = vec![["name", "value", "unit", "source", "description"].map(String::from)];
+    rows.extend(Constant::iter().filter_map(|constant| {
+        let names = constant.names();
+        let value = ctx.constants.get(names[0])?;
+        Some([
+            names.join(", "),
+            scientific(&value.value),
+            constant.unit().unwrap_or_default().to_owned(),
+            constant.source().unwrap_or_default().to_owned(),
+            constant.description().to_owned(),
+        ])
+    }));
+
+    let builtin: HashSet<&str> = Constant::iter()
+        .flat_map(|constant| constant.names().iter().copied())
+        .collect();
+    let mut custom: Vec<_> = ctx
+        .constants
+        .iter()
+        .filter(|(name, _)| !builtin.contains(name.as_str()))
+        .collect();
+    custom.sort_by_key(|(name, _)| name.as_str());
+    rows.extend(custom.into_iter().map(|(name, value)| {
+        let unit = if value.dimension.is_dimensionless() {
+            String::new()
+        } else {
+            value.dimension.to_string()
+        };
+        [
+            name.clone(),
+            scientific(&value.value),
+            unit,
+            String::new(),
+            String::new(),
+        ]
+    }));
+
+    let mut widths = [0; 5];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let mut out = String::new();
+    for row in &rows {
+        let line = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+fn eval_and_print(ctx: &mut Context<Quantity>, expr: &str) -> Result<()> {
+    if expr.trim() == ":constants" {
+        print!("{}", constants_table(ctx));
+        return Ok(());
+    }
     match ctx.evaluate_annotated(expr) {
+        Ok(n) if ctx.inexact.get() => println!("{} (inexact)", n),
         Ok(n) => println!("{}", n),
         Err(Error::Eval(err)) => bail!(err),
         Err(Error::Format(err)) => bail!(err),
+        Err(err @ (Error::InvalidName(_) | Error::NameConflict(..) | Error::ConstantSyntax(_))) => {
+            bail!(err)
+        }
         Err(Error::Parse(err)) => {
             use lalrpop_util::ParseError::{
                 ExtraToken, InvalidToken, UnrecognizedEof, UnrecognizedToken, User,
@@ -94,8 +229,7 @@ fn eval_and_print(ctx: &mut Context, expr: &str) -> Result<()> {
     Ok(())
 }
 
-fn shell() -> Result<()> {
-    let mut ctx = Context::default();
+fn shell(mut ctx: Context<Quantity>) -> Result<()> {
     let mut rl = rustyline::Editor::<(), _>::new()?;
 
     loop {
@@ -120,8 +254,8 @@ fn main() -> Result<()> {
     }
 
     if opt.expression.is_empty() {
-        shell()
+        shell(opt.context()?)
     } else {
-        eval_and_print(&mut Context::default(), &opt.expr())
+        eval_and_print(&mut opt.context()?, &opt.expr())
     }
 }
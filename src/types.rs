@@ -0,0 +1,145 @@
+//! Numeric types which expressions can be evaluated in.
+//!
+//! [`Value`][crate::Value] is the default, and supports every operator, function, and constant.
+//! To calculate with a type of your own, implement [`Calcable`] for it and evaluate
+//! expressions in a [`Context<YourType>`][crate::Context].
+
+use std::{
+    cmp::Ordering,
+    fmt,
+    time::{Duration, SystemTime},
+};
+
+use num_runtime_fmt::NumFmt;
+
+use crate::{
+    ast::{Constant, Function, InfixOperator, PrefixOperator},
+    Backend, MachineType, ValueError,
+};
+
+/// A numeric type which expressions can be evaluated in.
+///
+/// Operators, functions, and constants are each passed to a single method as the
+/// [`ast`][crate::ast] enum which names them, so an implementation can support
+/// whichever of them make sense for its type and return [`ValueError::Unsupported`] for the rest.
+///
+/// Comparisons and the logical operators are not passed to [`Calcable::infix`]: they are
+/// implemented in terms of [`PartialEq`], [`Calcable::numeric_cmp`], and [`Calcable::is_truthy`],
+/// and produce `1` or `0` via `From<bool>`.
+pub trait Calcable: Clone + PartialEq + fmt::Debug + fmt::Display + From<bool> {
+    /// Parse a numeric literal.
+    ///
+    /// `radix` is 2, 8, 10, or 16. The literal may contain `_` separators, and keeps its
+    /// prefix (such as `0x`) if it had one. Only base-10 literals may have a fractional part
+    /// or an exponent.
+    fn parse(literal: &str, radix: u32, backend: Backend) -> Result<Self, ValueError>;
+
+    /// The value of a built-in constant.
+    ///
+    /// A new [`Context`][crate::Context] binds each constant for which this succeeds to its
+    /// [names][Constant::names]; the rest are left unbound.
+    fn constant(constant: Constant) -> Result<Self, ValueError>;
+
+    /// The value of a unit of measure such as `km`, or `None` if `name` is not one.
+    ///
+    /// Names which are not constants or variables are looked up as units.
+    /// The default recognizes no units.
+    fn unit(_name: &str) -> Option<Self> {
+        None
+    }
+
+    /// The value of a date and time, such as the literal `2026-10-17T14:30` or the result of `now()`.
+    ///
+    /// The default supports no dates, so it is an error.
+    fn datetime(_time: SystemTime) -> Result<Self, ValueError> {
+        Err(ValueError::Unsupported("dates and times".into()))
+    }
+
+    /// The value of a duration, such as the literal `3h 20m` or the time of day `T14:30`.
+    ///
+    /// The default supports no durations, so it is an error.
+    fn duration(_duration: Duration) -> Result<Self, ValueError> {
+        Err(ValueError::Unsupported("durations".into()))
+    }
+
+    /// Apply a prefix operator.
+    ///
+    /// `backend` is that of the context, as for [`Calcable::parse`].
+    fn prefix(
+        operator: PrefixOperator,
+        operand: Self,
+        backend: Backend,
+    ) -> Result<Self, ValueError>;
+
+    /// Apply an arithmetic or bitwise infix operator.
+    fn infix(
+        operator: InfixOperator,
+        left: Self,
+        right: Self,
+        backend: Backend,
+    ) -> Result<Self, ValueError>;
+
+    /// Apply a built-in function.
+    ///
+    /// The number of arguments has already been checked against [`Function::arity`].
+    fn function(function: Function, args: Vec<Self>, backend: Backend) -> Result<Self, ValueError>;
+
+    /// Compare the logical values of `self` and `other`, or `None` if they are unordered.
+    fn numeric_cmp(&self, other: &Self) -> Option<Ordering>;
+
+    /// Whether this value counts as true in a logical context.
+    fn is_truthy(&self) -> bool;
+
+    /// Render this value according to an output format specifier.
+    fn format(&self, format: &NumFmt) -> Result<String, num_runtime_fmt::Error>;
+
+    /// Render a result of the machine type `ty` according to an output format specifier.
+    ///
+    /// This lets an `f32` be rendered at its own precision. The default renders it as by
+    /// [`Calcable::format`].
+    fn format_as(
+        &self,
+        format: &NumFmt,
+        _ty: MachineType,
+    ) -> Result<String, num_runtime_fmt::Error> {
+        self.format(format)
+    }
+
+    /// Render this value in polar form.
+    ///
+    /// The default renders it as by [`Calcable::format`], which suits types without complex numbers.
+    fn format_polar(&self, format: &NumFmt) -> Result<String, num_runtime_fmt::Error> {
+        self.format(format)
+    }
+
+    /// Express `self` as a number of `unit`s, for a conversion such as `60 mph to km/h`.
+    ///
+    /// The default supports no units, so it is an error.
+    fn convert(self, _unit: Self) -> Result<Self, ValueError> {
+        Err(ValueError::Unsupported("unit conversion".into()))
+    }
+
+    /// Convert `self` to a machine type, for a cast such as `x as u8` or a literal such as `255u8`.
+    ///
+    /// The default supports no machine types, so it is an error.
+    fn cast(self, _ty: MachineType) -> Result<Self, ValueError> {
+        Err(ValueError::Unsupported("casts".into()))
+    }
+
+    /// Whether an operation which produced `result` from `operands` lost precision,
+    /// such as by rounding an integer too large for a float to the nearest float.
+    ///
+    /// Each operator, function, and cast is checked against the context's
+    /// [`Precision`][crate::Precision] policy.
+    /// The default reports no loss, which suits exact types and types which are always rounded.
+    fn lost_precision(_operands: &[Self], _result: &Self) -> bool {
+        false
+    }
+
+    /// Adjust the result of a complete statement as `backend` requires.
+    ///
+    /// The default leaves it unchanged.
+    fn finish(self, _backend: Backend) -> Self {
+        self
+    }
+}
@@ -1,17 +1,163 @@
 use std::ops::{self, AddAssign, DivAssign, MulAssign, RemAssign, SubAssign};
 
-use super::dispatch_operation;
+use num_bigint::BigInt;
+use num_complex::Complex64;
+use num_rational::BigRational;
+use num_traits::Zero as _;
+use rust_decimal::Decimal;
+
+use super::{dispatch_operation, Order, MAX_ARBITRARY_BITS};
 use crate::Value;
 
-// shim for the missing method on f64
-trait CheckedAdd: Sized + ops::Add<Output = Self> {
-    fn checked_add(self, rhs: Self) -> Option<Self>;
+/// Arithmetic which reports when its result does not fit into the operand type.
+///
+/// Floats and complex numbers never fail. Arbitrary-precision integers and fractions fail only when the result
+/// could exceed [`MAX_ARBITRARY_BITS`]. Decimals fail when the result is out of their range.
+/// The remainder of a division by zero fails for every order but floats and complex numbers.
+trait Checked: Sized {
+    fn checked_add(&self, rhs: &Self) -> Option<Self>;
+    fn checked_sub(&self, rhs: &Self) -> Option<Self>;
+    fn checked_mul(&self, rhs: &Self) -> Option<Self>;
+    fn checked_rem(&self, rhs: &Self) -> Option<Self>;
+}
+
+macro_rules! impl_checked_for_primitive_int {
+    ($($t:ty),*) => {
+        $(
+            impl Checked for $t {
+                fn checked_add(&self, rhs: &Self) -> Option<Self> {
+                    <$t>::checked_add(*self, *rhs)
+                }
+
+                fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+                    <$t>::checked_sub(*self, *rhs)
+                }
+
+                fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+                    <$t>::checked_mul(*self, *rhs)
+                }
+
+                fn checked_rem(&self, rhs: &Self) -> Option<Self> {
+                    <$t>::checked_rem(*self, *rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_for_primitive_int!(u64, u128, i64, i128);
+
+impl Checked for BigInt {
+    fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        (self.bits().max(rhs.bits()) < MAX_ARBITRARY_BITS).then(|| self + rhs)
+    }
+
+    fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        (self.bits().max(rhs.bits()) < MAX_ARBITRARY_BITS).then(|| self - rhs)
+    }
+
+    fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        (self.bits() + rhs.bits() <= MAX_ARBITRARY_BITS).then(|| self * rhs)
+    }
+
+    fn checked_rem(&self, rhs: &Self) -> Option<Self> {
+        (!rhs.is_zero()).then(|| self % rhs)
+    }
+}
+
+/// `n`, if neither its numerator nor its denominator exceeds [`MAX_ARBITRARY_BITS`].
+fn bounded(n: BigRational) -> Option<BigRational> {
+    (n.numer().bits().max(n.denom().bits()) <= MAX_ARBITRARY_BITS).then_some(n)
+}
+
+impl Checked for BigRational {
+    fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        bounded(self + rhs)
+    }
+
+    fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        bounded(self - rhs)
+    }
+
+    fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        bounded(self * rhs)
+    }
+
+    fn checked_rem(&self, rhs: &Self) -> Option<Self> {
+        (!rhs.is_zero()).then(|| self % rhs)
+    }
+}
+
+impl Checked for Decimal {
+    fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        Decimal::checked_add(*self, *rhs)
+    }
+
+    fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        Decimal::checked_sub(*self, *rhs)
+    }
+
+    fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        Decimal::checked_mul(*self, *rhs)
+    }
+
+    fn checked_rem(&self, rhs: &Self) -> Option<Self> {
+        Decimal::checked_rem(*self, *rhs)
+    }
 }
 
-impl CheckedAdd for f64 {
-    fn checked_add(self, rhs: Self) -> Option<Self> {
+impl Checked for f64 {
+    fn checked_add(&self, rhs: &Self) -> Option<Self> {
         Some(self + rhs)
     }
+
+    fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        Some(self - rhs)
+    }
+
+    fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        Some(self * rhs)
+    }
+
+    fn checked_rem(&self, rhs: &Self) -> Option<Self> {
+        Some(self % rhs)
+    }
+}
+
+impl Checked for Complex64 {
+    fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        Some(self + rhs)
+    }
+
+    fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        Some(self - rhs)
+    }
+
+    fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        Some(self * rhs)
+    }
+
+    fn checked_rem(&self, rhs: &Self) -> Option<Self> {
+        Some(self % rhs)
+    }
+}
+
+/// Apply a [`Checked`] operation, promoting `$lhs` and retrying until it succeeds.
+///
+/// This always terminates, because floats never fail.
+macro_rules! promote_until_checked {
+    ($lhs:expr, $rhs:expr, $method:ident) => {{
+        loop {
+            let mut rhs = $rhs.clone();
+            if let Some(result) =
+                dispatch_operation!($lhs, rhs, n, |rhs| Checked::$method(&*n, &rhs)
+                    .map(Value::from))
+            {
+                break result;
+            }
+            $lhs.promote();
+        }
+    }};
 }
 
 impl<Rhs> ops::AddAssign<Rhs> for Value
@@ -19,12 +165,8 @@ where
     Rhs: Into<Value>,
 {
     fn add_assign(&mut self, rhs: Rhs) {
-        let mut rhs = rhs.into();
-        *self = dispatch_operation!(self, rhs, n, |rhs| (*n).checked_add(rhs).map(Value::from))
-            .unwrap_or_else(|| {
-                self.promote();
-                dispatch_operation!(self, rhs, n, |rhs| Value::from(*n + rhs))
-            });
+        let rhs = rhs.into();
+        *self = promote_until_checked!(self, rhs, checked_add);
     }
 }
 
@@ -44,11 +186,11 @@ where
     Rhs: Into<Value>,
 {
     fn sub_assign(&mut self, rhs: Rhs) {
-        let mut rhs = rhs.into();
+        let rhs = rhs.into();
         if rhs > *self {
             self.promote_to_signed();
         }
-        dispatch_operation!(self, rhs, n, |rhs| *n -= rhs);
+        *self = promote_until_checked!(self, rhs, checked_sub);
     }
 }
 
@@ -69,8 +211,8 @@ where
     Rhs: Into<Value>,
 {
     fn mul_assign(&mut self, rhs: Rhs) {
-        let mut rhs = rhs.into();
-        dispatch_operation!(self, rhs, n, |rhs| *n *= rhs);
+        let rhs = rhs.into();
+        *self = promote_until_checked!(self, rhs, checked_mul);
     }
 }
 
@@ -91,10 +233,34 @@ where
     Rhs: Into<Value>,
 {
     fn div_assign(&mut self, rhs: Rhs) {
-        self.promote_to_float();
         let mut rhs = rhs.into();
-        rhs.promote_to_float();
-        dispatch_operation!(self, rhs, n, |rhs| *n /= rhs);
+        if self.order() == Order::Complex || rhs.order() == Order::Complex {
+            let quotient = *self.promote_to_complex() / *rhs.promote_to_complex();
+            *self = quotient.into();
+            return;
+        }
+        // integers and fractions divide exactly, and decimals as precisely as they can, except by zero
+        if rhs != Value::UnsignedInt(0) {
+            self.promote_to_rational();
+            rhs.promote_to_rational();
+            self.match_orders(&mut rhs);
+            match (&*self, &rhs) {
+                (Value::Rational(l), Value::Rational(r)) => {
+                    if let Some(quotient) = bounded(l / r) {
+                        *self = quotient.into();
+                        return;
+                    }
+                }
+                (Value::Decimal(l), Value::Decimal(r)) => {
+                    if let Some(quotient) = l.checked_div(*r) {
+                        *self = quotient.into();
+                        return;
+                    }
+                }
+                _ => {}
+            }
+        }
+        *self.promote_to_float() /= *rhs.promote_to_float();
     }
 }
 
@@ -114,9 +280,12 @@ impl<Rhs> ops::RemAssign<Rhs> for Value
 where
     Rhs: Into<Value>,
 {
+    /// Take the remainder, promoting on overflow as for `i64::MIN % -1`.
+    ///
+    /// Like division, the remainder of a division by zero is a float: `NaN`.
     fn rem_assign(&mut self, rhs: Rhs) {
-        let mut rhs = rhs.into();
-        dispatch_operation!(self, rhs, n, |rhs| *n %= rhs);
+        let rhs = rhs.into();
+        *self = promote_until_checked!(self, rhs, checked_rem);
     }
 }
 
@@ -153,7 +322,11 @@ impl ops::Neg for Value {
             // everything else is simple negation
             Value::SignedInt(n) => (-n).into(),
             Value::SignedBigInt(n) => (-n).into(),
+            Value::ArbitraryInt(n) => (-n).into(),
+            Value::Rational(n) => (-n).into(),
+            Value::Decimal(n) => (-n).into(),
             Value::Float(n) => (-n).into(),
+            Value::Complex(n) => (-n).into(),
         }
     }
 }
@@ -284,11 +457,80 @@ mod tests {
     ) {
         let left = left.into();
         let right = right.into();
-        // division always produces a float
-        let expect_order = Order::Float;
+        // division is exact unless either side is a float
+        let expect_float = left.order() == Order::Float || right.order() == Order::Float;
 
         let result = left / right;
-        assert_eq!(result.order(), expect_order);
+        assert_eq!(result.order() == Order::Float, expect_float);
+    }
+
+    #[test]
+    fn integer_division_is_exact() {
+        let third = Value::from(1_u64) / 3_u64;
+        let sixth = Value::from(1_u64) / 6_u64;
+        assert_eq!(third.order(), Order::Rational);
+        let result = third + sixth;
+        assert!(result.strict_eq(&Value::from(BigRational::new(1.into(), 2.into()))));
+    }
+
+    #[rstest]
+    #[case(10_u64, 5_u64)]
+    #[case(-30_i64, 3_u64)]
+    #[case(u128::MAX, 5_u64)]
+    fn integral_quotient_is_an_integer(#[case] left: impl Into<Value>, #[case] right: u64) {
+        let result = left.into() / right;
+        assert!(result.order() < Order::Rational, "{result:?}");
+    }
+
+    #[test]
+    fn fractions_cancel_to_integers() {
+        let half = Value::from(1_u64) / 2_u64;
+        let result = half.clone() + half;
+        assert!(result.strict_eq(&Value::UnsignedInt(1)));
+    }
+
+    #[test]
+    fn fraction_and_float_is_float() {
+        let third = Value::from(1_u64) / 3_u64;
+        let result = third * 1.5_f64;
+        assert!(result.strict_eq(&Value::Float(0.5)));
+    }
+
+    #[test]
+    fn fraction_div_by_zero_produces_infinity() {
+        let third = Value::from(1_u64) / 3_u64;
+        assert_eq!(third / 0_u64, f64::INFINITY.into());
+    }
+
+    #[test]
+    fn decimal_arithmetic_is_exact_in_base_10() {
+        let tenth = Value::Decimal(Decimal::new(1, 1));
+        let fifth = Value::Decimal(Decimal::new(2, 1));
+        let result = tenth + fifth;
+        assert!(result.strict_eq(&Value::Decimal(Decimal::new(3, 1))));
+    }
+
+    #[rstest]
+    #[case(Value::from(3_u64))]
+    #[case(Value::from(1_u64) / 3_u64)]
+    #[case(Value::Decimal(Decimal::new(3, 0)))]
+    fn decimal_division_is_decimal(#[case] divisor: Value) {
+        let dollars = Value::Decimal(Decimal::new(1000, 2));
+        let result = dollars / divisor;
+        assert_eq!(result.order(), Order::Decimal);
+    }
+
+    #[test]
+    fn decimal_and_integer_stays_decimal() {
+        let price = Value::Decimal(Decimal::new(999, 2));
+        let result = price * 3_u64;
+        assert!(result.strict_eq(&Value::Decimal(Decimal::new(2997, 2))));
+    }
+
+    #[test]
+    fn decimal_overflow_is_float() {
+        let result = Value::Decimal(Decimal::MAX) * 2_u64;
+        assert_eq!(result.order(), Order::Float);
     }
 
     #[test]
@@ -306,11 +548,11 @@ mod tests {
     ) {
         let mut left = left.into();
         let right = right.into();
-        // division always produces a float
-        let expect_order = Order::Float;
+        // division is exact unless either side is a float
+        let expect_float = left.order() == Order::Float || right.order() == Order::Float;
 
         left /= right;
-        assert_eq!(left.order(), expect_order);
+        assert_eq!(left.order() == Order::Float, expect_float);
     }
 
     // ---------- REM ----------
@@ -328,11 +570,33 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
-    fn rem_by_zero_panics() {
-        let left: Value = 10_i64.into();
-        let right: Value = 0_i64.into();
-        let _ = left % right;
+    fn rem_of_fractions() {
+        let seven_halves = Value::from(7_u64) / 2_u64;
+        let result = seven_halves % (Value::from(3_u64) / 4_u64);
+        assert!(result.strict_eq(&Value::from(BigRational::new(1.into(), 2.into()))));
+    }
+
+    #[rstest]
+    fn rem_by_zero_is_nan(
+        #[values(10_u64, 20_u128, -30_i64, -40_i128, 5.5_f64)] left: impl Into<Value>,
+        #[values(0_u64, 0_u128, 0_i64, 0_i128, 0.0_f64)] right: impl Into<Value>,
+    ) {
+        let result = left.into() % right.into();
+        assert!(
+            matches!(result, Value::Float(n) if n.is_nan()),
+            "{result:?}"
+        );
+    }
+
+    #[rstest]
+    #[case(i64::MIN, -1_i64)]
+    #[case(i128::MIN, -1_i64)]
+    fn rem_of_minimum_by_minus_one_promotes(
+        #[case] left: impl Into<Value>,
+        #[case] right: impl Into<Value>,
+    ) {
+        let result = left.into() % right.into();
+        assert_eq!(result, Value::from(0_u64));
     }
 
     #[rstest]
@@ -402,7 +666,7 @@ mod tests {
     #[test]
     fn inf_div_inf_is_nan_like() {
         let inf: Value = (f64::INFINITY).into();
-        let result = inf / inf;
+        let result = inf.clone() / inf;
         assert!(matches!(result, Value::Float(f) if f.is_nan()));
     }
 
@@ -436,7 +700,47 @@ mod tests {
     fn i128_min_neg_promotes() {
         let val: Value = i128::MIN.into();
         let result = -val;
-        assert!(matches!(result, Value::Float(_)));
+        assert!(matches!(result, Value::ArbitraryInt(_)));
+        assert_eq!(result, Value::from(-BigInt::from(i128::MIN)));
+    }
+
+    #[test]
+    fn i128_max_plus_one_promotes_to_arbitrary() {
+        let left: Value = i128::MAX.into();
+        let result = left + 1_u64;
+        assert!(matches!(result, Value::ArbitraryInt(_)));
+        assert_eq!(result, Value::from(BigInt::from(i128::MAX) + 1));
+    }
+
+    #[test]
+    fn i128_min_minus_one_promotes_to_arbitrary() {
+        let left: Value = i128::MIN.into();
+        let result = left - 1_u64;
+        assert!(matches!(result, Value::ArbitraryInt(_)));
+        assert_eq!(result, Value::from(BigInt::from(i128::MIN) - 1));
+    }
+
+    #[rstest]
+    #[case(u64::MAX, u64::MAX, Order::UnsignedBigInt)]
+    #[case(u128::MAX, u128::MAX, Order::ArbitraryInt)]
+    #[case(i64::MIN, i64::MAX, Order::SignedBigInt)]
+    #[case(i128::MIN, 2_u64, Order::ArbitraryInt)]
+    fn mul_overflow_is_exact(
+        #[case] left: impl Into<Value> + Into<BigInt> + Copy,
+        #[case] right: impl Into<Value> + Into<BigInt> + Copy,
+        #[case] expect_order: Order,
+    ) {
+        let result = Into::<Value>::into(left) * Into::<Value>::into(right);
+        let expect = Into::<BigInt>::into(left) * Into::<BigInt>::into(right);
+        assert_eq!(result.order(), expect_order);
+        assert_eq!(result, Value::from(expect));
+    }
+
+    #[test]
+    fn arbitrary_mul_beyond_limit_is_float() {
+        let huge: Value = (BigInt::from(1) << (MAX_ARBITRARY_BITS - 1)).into();
+        let result = huge.clone() * huge;
+        assert!(matches!(result, Value::Float(f) if f.is_infinite()));
     }
 
     // ---------- CROSS-TYPE INTERACTIONS ----------
@@ -463,4 +767,38 @@ mod tests {
         let result = left * right;
         assert!(matches!(result, Value::Float(_)));
     }
+
+    // ---------- COMPLEX ----------
+    #[test]
+    fn complex_arithmetic() {
+        let z = Value::from(Complex64::new(1.0, 2.0));
+        let w = Value::from(Complex64::new(3.0, -1.0));
+        assert!((z.clone() + w.clone()).strict_eq(&Complex64::new(4.0, 1.0).into()));
+        assert!((z.clone() - w.clone()).strict_eq(&Complex64::new(-2.0, 3.0).into()));
+        assert!((z.clone() * w.clone()).strict_eq(&Complex64::new(5.0, 5.0).into()));
+        assert!((z / w).strict_eq(&Complex64::new(0.1, 0.7).into()));
+    }
+
+    #[test]
+    fn complex_with_real_imaginary_part_is_float() {
+        let result = Value::I * Value::I;
+        assert!(result.strict_eq(&Value::Float(-1.0)), "{result:?}");
+        let result = Value::from(Complex64::new(2.0, 3.0)) - Value::I * 3_u64;
+        assert!(result.strict_eq(&Value::Float(2.0)), "{result:?}");
+    }
+
+    #[rstest]
+    fn complex_and_real_promotes_to_complex(
+        #[values(2_u64, -2_i64, u128::MAX, 2.5_f64)] real: impl Into<Value>,
+    ) {
+        let result = real.into() + Value::I;
+        assert_eq!(result.order(), Order::Complex);
+    }
+
+    #[test]
+    fn complex_and_fraction_promotes_to_complex() {
+        let half = Value::from(1_u64) / 2_u64;
+        let result = half * Value::I;
+        assert!(result.strict_eq(&Complex64::new(0.0, 0.5).into()));
+    }
 }
@@ -1,7 +1,10 @@
 use std::ops;
 
-use super::{dispatch_operation, Error, Result};
-use crate::Value;
+use num_bigint::BigInt;
+use num_traits::{Euclid as _, One as _, Signed as _, ToPrimitive as _};
+
+use super::{dispatch_operation, ArithmeticError, Error, Result, MAX_ARBITRARY_BITS};
+use crate::{ast::Function, Value};
 
 impl Value {
     /// Compute this value left-shifted by `other` bits, wrapping the bits around.
@@ -12,7 +15,11 @@ impl Value {
             Value::UnsignedBigInt(n) => Ok(n.rotate_left(shift).into()),
             Value::SignedInt(n) => Ok(n.rotate_left(shift).into()),
             Value::SignedBigInt(n) => Ok(n.rotate_left(shift).into()),
+            Value::ArbitraryInt(_) => Err(Error::Unbounded),
+            Value::Rational(_) => Err(Error::ImproperlyRational),
+            Value::Decimal(_) => Err(Error::ImproperlyDecimal),
             Value::Float(_) => Err(Error::ImproperlyFloat),
+            Value::Complex(_) => Err(Error::ImproperlyComplex),
         }
     }
 
@@ -24,9 +31,217 @@ impl Value {
             Value::UnsignedBigInt(n) => Ok(n.rotate_right(shift).into()),
             Value::SignedInt(n) => Ok(n.rotate_right(shift).into()),
             Value::SignedBigInt(n) => Ok(n.rotate_right(shift).into()),
+            Value::ArbitraryInt(_) => Err(Error::Unbounded),
+            Value::Rational(_) => Err(Error::ImproperlyRational),
+            Value::Decimal(_) => Err(Error::ImproperlyDecimal),
             Value::Float(_) => Err(Error::ImproperlyFloat),
+            Value::Complex(_) => Err(Error::ImproperlyComplex),
+        }
+    }
+
+    /// Apply a bit-manipulation function, such as `popcount`, to its arguments.
+    ///
+    /// `width` is the width in bits for the functions which accept one, unless an argument gives it.
+    pub(crate) fn bit_function(function: Function, args: Vec<Value>, width: Option<u32>) -> Result {
+        let mut args = args.into_iter();
+        let x = args.next().expect("arity has been checked");
+        // the other arguments are bit positions or widths, apart from the field of `setbits`
+        let field = match function {
+            Function::Setbits => args.next_back(),
+            _ => None,
+        };
+        let mut positions = args.map(|arg| arg.as_u32());
+        let mut next = || positions.next().transpose();
+        match function {
+            Function::Popcount => x.popcount(next()?.or(width)),
+            Function::Clz => x.clz(next()?.or(width)),
+            Function::Ctz => x.ctz(next()?.or(width)),
+            Function::Bswap => x.bswap(next()?.or(width)),
+            Function::Bitrev => x.bitrev(next()?.or(width)),
+            Function::Parity => x.parity(next()?.or(width)),
+            Function::Ilog2 => x.ilog2(),
+            Function::NextPow2 => x.next_pow2(),
+            Function::IsolateLowest => x.isolate_lowest(),
+            Function::Getbits => {
+                let hi = next()?.expect("arity has been checked");
+                x.getbits(hi, next()?.unwrap_or(hi))
+            }
+            Function::Setbits => {
+                let hi = next()?.expect("arity has been checked");
+                let lo = next()?.expect("arity has been checked");
+                x.setbits(hi, lo, field.expect("arity has been checked"))
+            }
+            _ => unreachable!("only bitwise functions are dispatched here"),
+        }
+    }
+
+    /// Count the set bits of this integer, `width` bits wide.
+    ///
+    /// As for each of the functions which take a `width`, negative integers are in two's complement,
+    /// and by default the width is 64 bits, or 128 for integers which do not fit in 64.
+    pub fn popcount(self, width: Option<u32>) -> Result {
+        Ok(self.bits(width)?.pattern.count_ones().into())
+    }
+
+    /// Count the leading zero bits of this integer, `width` bits wide.
+    pub fn clz(self, width: Option<u32>) -> Result {
+        let bits = self.bits(width)?;
+        Ok((bits.pattern.leading_zeros() - bits.unused()).into())
+    }
+
+    /// Count the trailing zero bits of this integer, `width` bits wide: all of them for `0`.
+    pub fn ctz(self, width: Option<u32>) -> Result {
+        let bits = self.bits(width)?;
+        Ok(bits.pattern.trailing_zeros().min(bits.width).into())
+    }
+
+    /// Reverse the order of the bytes of this integer, `width` bits wide.
+    ///
+    /// The width must be a whole number of bytes. Negative integers produce signed results.
+    pub fn bswap(self, width: Option<u32>) -> Result {
+        let bits = self.bits(width)?;
+        if bits.width % 8 != 0 {
+            return Err(Error::Width(bits.width));
         }
+        Ok(bits.read(bits.pattern.swap_bytes() >> bits.unused()))
     }
+
+    /// Reverse the order of the bits of this integer, `width` bits wide.
+    ///
+    /// Negative integers produce signed results.
+    pub fn bitrev(self, width: Option<u32>) -> Result {
+        let bits = self.bits(width)?;
+        Ok(bits.read(bits.pattern.reverse_bits() >> bits.unused()))
+    }
+
+    /// `1` if this integer has an odd number of set bits, `width` bits wide, and `0` otherwise.
+    pub fn parity(self, width: Option<u32>) -> Result {
+        Ok((self.bits(width)?.pattern.count_ones() % 2).into())
+    }
+
+    /// The base-2 logarithm of this integer, rounded down.
+    pub fn ilog2(self) -> Result {
+        let n = self.integer()?;
+        if !n.is_positive() {
+            return Err(Error::Domain(<&str>::from(Function::Ilog2).to_owned()));
+        }
+        Ok((n.bits() - 1).into())
+    }
+
+    /// The least power of 2 which is not less than this integer.
+    pub fn next_pow2(self) -> Result {
+        let n = self.integer()?;
+        if n.is_negative() {
+            return Err(Error::Domain(<&str>::from(Function::NextPow2).to_owned()));
+        }
+        if n <= BigInt::one() {
+            return Ok(1_u64.into());
+        }
+        Ok(Value::narrowest(BigInt::one() << (n - 1_u32).bits()))
+    }
+
+    /// The lowest set bit of this integer, or `0` if it has none: `x & -x`.
+    pub fn isolate_lowest(self) -> Result {
+        let n = self.integer()?;
+        Ok(Value::narrowest(&n & -&n))
+    }
+
+    /// Extract bits `hi` down to `lo` of this integer, as in `x[7:4]`.
+    ///
+    /// Negative integers are in two's complement, so `-1[7:0]` is `255`.
+    pub fn getbits(self, hi: u32, lo: u32) -> Result {
+        let mask = field_mask(hi, lo)?;
+        (self >> lo)? & mask
+    }
+
+    /// Replace bits `hi` down to `lo` of this integer with the low bits of `field`.
+    pub fn setbits(self, hi: u32, lo: u32, field: Value) -> Result {
+        let mask = field_mask(hi, lo)?;
+        let clear = Value::narrowest(!(mask.integer()? << lo));
+        let field = ((field & mask)? << lo)?;
+        (self & clear)? | field
+    }
+
+    /// The value of an integer of any order.
+    fn integer(&self) -> Result<BigInt> {
+        match self {
+            Value::UnsignedInt(n) => Ok((*n).into()),
+            Value::UnsignedBigInt(n) => Ok((*n).into()),
+            Value::SignedInt(n) => Ok((*n).into()),
+            Value::SignedBigInt(n) => Ok((*n).into()),
+            Value::ArbitraryInt(n) => Ok(n.clone()),
+            Value::Rational(_) => Err(Error::ImproperlyRational),
+            Value::Decimal(_) => Err(Error::ImproperlyDecimal),
+            Value::Float(_) => Err(Error::ImproperlyFloat),
+            Value::Complex(_) => Err(Error::ImproperlyComplex),
+        }
+    }
+
+    /// The bit pattern of this integer, `width` bits wide.
+    fn bits(&self, width: Option<u32>) -> Result<Bits> {
+        let n = self.integer()?;
+        let width = match width {
+            Some(width @ 1..=128) => width,
+            Some(width) => return Err(Error::Width(width)),
+            None if fits(&n, 64) => 64,
+            None if fits(&n, 128) => 128,
+            None => return Err(Error::Unbounded),
+        };
+        if !fits(&n, width) {
+            return Err(ArithmeticError::Overflow.into());
+        }
+        let pattern = n
+            .rem_euclid(&(BigInt::one() << width))
+            .to_u128()
+            .expect("the pattern is at most 128 bits wide");
+        Ok(Bits {
+            pattern,
+            width,
+            negative: n.is_negative(),
+        })
+    }
+}
+
+/// The two's complement bit pattern of an integer.
+struct Bits {
+    pattern: u128,
+    width: u32,
+    negative: bool,
+}
+
+impl Bits {
+    /// The number of high bits of the pattern which are outside the width.
+    fn unused(&self) -> u32 {
+        u128::BITS - self.width
+    }
+
+    /// Read another pattern of the same width as an integer, which is signed if this one was negative.
+    fn read(&self, pattern: u128) -> Value {
+        let n = if self.negative {
+            BigInt::from(((pattern << self.unused()) as i128) >> self.unused())
+        } else {
+            BigInt::from(pattern)
+        };
+        Value::narrowest(n)
+    }
+}
+
+/// The mask of the low bits of a field which spans bits `hi` down to `lo`.
+fn field_mask(hi: u32, lo: u32) -> Result {
+    if hi < lo {
+        return Err(Error::BitRange(hi, lo));
+    }
+    if u64::from(hi) >= MAX_ARBITRARY_BITS {
+        return Err(ArithmeticError::Overflow.into());
+    }
+    Ok(Value::narrowest((BigInt::one() << (hi - lo + 1)) - 1_u32))
+}
+
+/// Whether `n` fits in `width` bits, as either a signed or an unsigned integer.
+fn fits(n: &BigInt, width: u32) -> bool {
+    let limit = BigInt::one() << width;
+    let least: BigInt = -(&limit >> 1_u32);
+    least <= *n && *n < limit
 }
 
 impl<Rhs> ops::Shl<Rhs> for Value
@@ -35,12 +250,45 @@ where
 {
     type Output = Result;
 
-    fn shl(mut self, rhs: Rhs) -> Self::Output {
-        let mut rhs = rhs.into();
-        dispatch_operation!(INTS: &mut self, rhs, n, |rhs| {
-            *n <<= rhs;
-            (*n).into()
-        })
+    /// Shift left, promoting as multiplication does if any set bit would be shifted out.
+    fn shl(self, rhs: Rhs) -> Self::Output {
+        let shift = rhs.into().as_u32()?;
+        let mut value = self;
+        loop {
+            // the shift is lossless when shifting back restores the original value
+            let shifted = match &value {
+                Value::UnsignedInt(n) => n
+                    .checked_shl(shift)
+                    .filter(|m| m >> shift == *n)
+                    .map(Value::from),
+                Value::UnsignedBigInt(n) => n
+                    .checked_shl(shift)
+                    .filter(|m| m >> shift == *n)
+                    .map(Value::from),
+                Value::SignedInt(n) => n
+                    .checked_shl(shift)
+                    .filter(|m| m >> shift == *n)
+                    .map(Value::from),
+                Value::SignedBigInt(n) => n
+                    .checked_shl(shift)
+                    .filter(|m| m >> shift == *n)
+                    .map(Value::from),
+                Value::ArbitraryInt(n) => {
+                    if n.bits() + u64::from(shift) > MAX_ARBITRARY_BITS {
+                        return Err(ArithmeticError::Overflow.into());
+                    }
+                    return Ok((n << shift).into());
+                }
+                Value::Rational(_) => return Err(Error::ImproperlyRational),
+                Value::Decimal(_) => return Err(Error::ImproperlyDecimal),
+                Value::Float(_) => return Err(Error::ImproperlyFloat),
+                Value::Complex(_) => return Err(Error::ImproperlyComplex),
+            };
+            if let Some(shifted) = shifted {
+                return Ok(shifted);
+            }
+            value.promote();
+        }
     }
 }
 
@@ -50,12 +298,22 @@ where
 {
     type Output = Result;
 
-    fn shr(mut self, rhs: Rhs) -> Self::Output {
-        let mut rhs = rhs.into();
-        dispatch_operation!(INTS: &mut self, rhs, n, |rhs| {
-            *n >>= rhs;
-            (*n).into()
-        })
+    fn shr(self, rhs: Rhs) -> Self::Output {
+        let shift = rhs.into().as_u32()?;
+        // shifting out every bit leaves only the sign
+        match self {
+            Value::UnsignedInt(n) => Ok(n.checked_shr(shift).unwrap_or(0).into()),
+            Value::UnsignedBigInt(n) => Ok(n.checked_shr(shift).unwrap_or(0).into()),
+            Value::SignedInt(n) => Ok(n.checked_shr(shift).unwrap_or(n >> (i64::BITS - 1)).into()),
+            Value::SignedBigInt(n) => {
+                Ok(n.checked_shr(shift).unwrap_or(n >> (i128::BITS - 1)).into())
+            }
+            Value::ArbitraryInt(n) => Ok((n >> shift).into()),
+            Value::Rational(_) => Err(Error::ImproperlyRational),
+            Value::Decimal(_) => Err(Error::ImproperlyDecimal),
+            Value::Float(_) => Err(Error::ImproperlyFloat),
+            Value::Complex(_) => Err(Error::ImproperlyComplex),
+        }
     }
 }
 
@@ -67,10 +325,7 @@ where
 
     fn bitand(mut self, rhs: Rhs) -> Self::Output {
         let mut rhs = rhs.into();
-        dispatch_operation!(INTS: &mut self, rhs, n, |rhs| {
-            *n &= rhs;
-            (*n).into()
-        })
+        dispatch_operation!(INTS: self, rhs, n, |rhs| Value::from(n & rhs))
     }
 }
 
@@ -82,10 +337,7 @@ where
 
     fn bitor(mut self, rhs: Rhs) -> Self::Output {
         let mut rhs = rhs.into();
-        dispatch_operation!(INTS: &mut self, rhs, n, |rhs| {
-            *n |= rhs;
-            (*n).into()
-        })
+        dispatch_operation!(INTS: self, rhs, n, |rhs| Value::from(n | rhs))
     }
 }
 
@@ -97,10 +349,7 @@ where
 
     fn bitxor(mut self, rhs: Rhs) -> Self::Output {
         let mut rhs = rhs.into();
-        dispatch_operation!(INTS: &mut self, rhs, n, |rhs| {
-            *n ^= rhs;
-            (*n).into()
-        })
+        dispatch_operation!(INTS: self, rhs, n, |rhs| Value::from(n ^ rhs))
     }
 }
 
@@ -113,7 +362,11 @@ impl ops::Not for Value {
             Value::UnsignedBigInt(n) => Ok((!n).into()),
             Value::SignedInt(n) => Ok((!n).into()),
             Value::SignedBigInt(n) => Ok((!n).into()),
+            Value::ArbitraryInt(n) => Ok((!n).into()),
+            Value::Rational(_) => Err(Error::ImproperlyRational),
+            Value::Decimal(_) => Err(Error::ImproperlyDecimal),
             Value::Float(_) => Err(Error::ImproperlyFloat),
+            Value::Complex(_) => Err(Error::ImproperlyComplex),
         }
     }
 }
@@ -135,6 +388,25 @@ mod tests {
         assert_ne!(result.order(), Order::Float);
     }
 
+    #[rstest]
+    #[case::unsigned(u64::MAX, 1, Order::UnsignedBigInt)]
+    #[case::unsigned_big(u128::MAX, 1, Order::ArbitraryInt)]
+    #[case::signed(i64::MIN, 1, Order::SignedBigInt)]
+    #[case::signed_big(i128::MIN, 1, Order::ArbitraryInt)]
+    #[case::into_sign_bit(1_i64, 63, Order::SignedBigInt)]
+    #[case::past_width(1_u64, 200, Order::ArbitraryInt)]
+    fn shl_overflow_promotes(
+        #[case] left: impl Into<Value>,
+        #[case] shift: u32,
+        #[case] expect_order: Order,
+    ) {
+        let left = left.into();
+        let expect = left.clone() * Value::from(2_u64).pow(shift).unwrap();
+        let result = (left << shift).unwrap();
+        assert_eq!(result.order(), expect_order);
+        assert_eq!(result, expect);
+    }
+
     #[test]
     fn shl_float_is_error() {
         let left: Value = 1.5_f64.into();
@@ -153,6 +425,17 @@ mod tests {
         assert_ne!(result.order(), Order::Float);
     }
 
+    #[rstest]
+    #[case(u64::MAX, 0_i64)]
+    #[case(u128::MAX, 0_i64)]
+    #[case(i64::MIN, -1_i64)]
+    #[case(i128::MIN, -1_i64)]
+    #[case(i64::MAX, 0_i64)]
+    fn shr_past_width(#[case] left: impl Into<Value>, #[case] expect: i64) {
+        let result = (left.into() >> 200_u32).unwrap();
+        assert_eq!(result, Value::from(expect));
+    }
+
     #[test]
     fn shr_float_is_error() {
         let left: Value = 8.0_f64.into();
@@ -271,6 +554,157 @@ mod tests {
         assert!(matches!(result, Err(Error::ImproperlyFloat)));
     }
 
+    // ---------- BIT FUNCTIONS ----------
+    #[rstest]
+    #[case::popcount(Function::Popcount, 0xff_u64, None, 8_u64)]
+    #[case::popcount_negative(Function::Popcount, -1_i64, None, 64_u64)]
+    #[case::popcount_width(Function::Popcount, -1_i64, Some(8), 8_u64)]
+    #[case::popcount_u128(Function::Popcount, u128::MAX, None, 128_u64)]
+    #[case::clz(Function::Clz, 1_u64, None, 63_u64)]
+    #[case::clz_width(Function::Clz, 1_u64, Some(32), 31_u64)]
+    #[case::clz_u128(Function::Clz, 1_u128 << 64, None, 63_u64)]
+    #[case::ctz(Function::Ctz, 0x80_u64, None, 7_u64)]
+    #[case::ctz_zero(Function::Ctz, 0_u64, Some(16), 16_u64)]
+    #[case::bswap(Function::Bswap, 0x1234_5678_u64, Some(32), 0x7856_3412_u64)]
+    #[case::bswap_default(Function::Bswap, 0x12_u64, None, 0x1200_0000_0000_0000_u64)]
+    #[case::bswap_negative(Function::Bswap, -2_i64, Some(16), -257_i64)]
+    #[case::bitrev(Function::Bitrev, 1_u64, Some(8), 0x80_u64)]
+    #[case::bitrev_negative(Function::Bitrev, -2_i64, Some(8), 127_i64)]
+    #[case::parity_odd(Function::Parity, 7_u64, None, 1_u64)]
+    #[case::parity_even(Function::Parity, 6_u64, None, 0_u64)]
+    #[case::ilog2(Function::Ilog2, 1000_u64, None, 9_u64)]
+    #[case::next_pow2(Function::NextPow2, 100_u64, None, 128_u64)]
+    #[case::next_pow2_exact(Function::NextPow2, 64_u64, None, 64_u64)]
+    #[case::next_pow2_zero(Function::NextPow2, 0_u64, None, 1_u64)]
+    #[case::isolate_lowest(Function::IsolateLowest, 0b10100_u64, None, 0b100_u64)]
+    #[case::isolate_lowest_negative(Function::IsolateLowest, -8_i64, None, 8_u64)]
+    fn bit_function(
+        #[case] function: Function,
+        #[case] x: impl Into<Value>,
+        #[case] width: Option<u32>,
+        #[case] expect: impl Into<Value>,
+    ) {
+        let result = Value::bit_function(function, vec![x.into()], width).unwrap();
+        assert_eq!(result, expect.into());
+    }
+
+    #[test]
+    fn bit_functions_on_arbitrary_integers() {
+        let big = || Value::from(num_bigint::BigInt::from(1) << 200);
+        assert!(matches!(big().popcount(None), Err(Error::Unbounded)));
+        assert_eq!(big().ilog2().unwrap(), 200_u64.into());
+        assert_eq!((big() + 1_u64).next_pow2().unwrap(), big() * 2_u64);
+        assert_eq!(big().isolate_lowest().unwrap(), big());
+    }
+
+    #[rstest]
+    #[case::float(1.0_f64.into(), Error::ImproperlyFloat)]
+    #[case::fraction(Value::from(1_u64) / 2_u64, Error::ImproperlyRational)]
+    #[case::zero_width(1_u64.into(), Error::Width(0))]
+    #[case::wide(1_u64.into(), Error::Width(129))]
+    #[case::too_narrow(300_u64.into(), Error::Arithmetic(ArithmeticError::Overflow))]
+    fn bit_function_errors(#[case] x: Value, #[case] expect: Error) {
+        let width = match expect {
+            Error::Width(width) => Some(width),
+            _ => Some(8),
+        };
+        let result = x.popcount(width).unwrap_err();
+        assert_eq!(
+            std::mem::discriminant(&result),
+            std::mem::discriminant(&expect)
+        );
+    }
+
+    #[rstest]
+    #[case::field(0xabcd_u64, 7, 4, 0xc_u64)]
+    #[case::bit(0b1000_u64, 3, 3, 1_u64)]
+    #[case::past_width(0xff_u64, 70, 64, 0_u64)]
+    #[case::negative(-1_i64, 7, 0, 0xff_u64)]
+    #[case::wide(u128::MAX, 127, 64, u64::MAX)]
+    fn getbits(
+        #[case] x: impl Into<Value>,
+        #[case] hi: u32,
+        #[case] lo: u32,
+        #[case] expect: impl Into<Value>,
+    ) {
+        assert_eq!(x.into().getbits(hi, lo).unwrap(), expect.into());
+    }
+
+    #[rstest]
+    #[case::field(0_u64, 7, 4, 0b1010_i64, 0xa0_u64)]
+    #[case::clear(0xff_u64, 3, 0, 0_i64, 0xf0_u64)]
+    #[case::truncates(0_u64, 3, 0, 0x1f_i64, 0xf_u64)]
+    #[case::all_ones(0_u64, 7, 4, -1_i64, 0xf0_u64)]
+    #[case::negative(-1_i64, 7, 0, 0_i64, -256_i64)]
+    #[case::promotes(1_u64, 64, 64, 1_i64, (1_u128 << 64) + 1)]
+    fn setbits(
+        #[case] x: impl Into<Value>,
+        #[case] hi: u32,
+        #[case] lo: u32,
+        #[case] field: i64,
+        #[case] expect: impl Into<Value>,
+    ) {
+        let result = x.into().setbits(hi, lo, field.into()).unwrap();
+        assert_eq!(result, expect.into());
+    }
+
+    #[test]
+    fn bit_field_errors() {
+        assert!(matches!(
+            Value::from(1_u64).getbits(3, 7),
+            Err(Error::BitRange(3, 7))
+        ));
+        assert!(matches!(
+            Value::from(1.0).getbits(3, 0),
+            Err(Error::ImproperlyFloat)
+        ));
+        assert!(matches!(
+            Value::from(1_u64).setbits(u32::MAX, 0, 1_u64.into()),
+            Err(Error::Arithmetic(ArithmeticError::Overflow))
+        ));
+    }
+
+    #[test]
+    fn bit_function_domain_errors() {
+        assert!(matches!(Value::from(0_u64).ilog2(), Err(Error::Domain(_))));
+        assert!(matches!(
+            Value::from(-4_i64).next_pow2(),
+            Err(Error::Domain(_))
+        ));
+        assert!(matches!(
+            Value::from(1_u64).bswap(Some(12)),
+            Err(Error::Width(12))
+        ));
+    }
+
+    // ---------- ARBITRARY PRECISION ----------
+    #[test]
+    fn arbitrary_bitwise_ops() {
+        let big = || Value::from(num_bigint::BigInt::from(1) << 200);
+        assert_eq!((big() & big()).unwrap(), big());
+        assert_eq!((big() | 1_u64).unwrap(), (big() + 1_u64));
+        assert_eq!((big() ^ big()).unwrap(), 0_u64.into());
+        assert_eq!((!big()).unwrap(), -big() - 1_u64);
+        assert_eq!((big() >> 199).unwrap(), 2_u64.into());
+        assert_eq!((big() << 1).unwrap(), big() * 2_u64);
+    }
+
+    #[test]
+    fn rotate_arbitrary_is_error() {
+        let val = Value::from(num_bigint::BigInt::from(1) << 200);
+        assert!(matches!(val.rotate_left(1), Err(Error::Unbounded)));
+    }
+
+    #[test]
+    fn bitwise_on_fraction_is_error() {
+        let half = Value::from(1_u64) / 2_u64;
+        assert!(matches!(
+            half.clone() & 1_u64,
+            Err(Error::ImproperlyRational)
+        ));
+        assert!(matches!(half << 1_u64, Err(Error::ImproperlyRational)));
+    }
+
     // ---------- CROSS-TYPE BITWISE ----------
     #[test]
     fn u64_and_u128_promotes_to_u128() {
@@ -0,0 +1,289 @@
+use std::cmp::Ordering;
+
+use num_runtime_fmt::NumFmt;
+
+use crate::{
+    ast::{Constant, Function, InfixOperator, PrefixOperator},
+    types::Calcable,
+    value::Order,
+    ArithmeticError, Backend, MachineType, Value, ValueError,
+};
+
+/// The implementation of a function, if it always takes exactly one argument.
+fn unary(function: Function) -> Option<fn(Value) -> Value> {
+    Some(match function {
+        Function::Abs => Value::abs,
+        Function::Ceil => Value::ceil,
+        Function::Floor => Value::floor,
+        Function::Round => Value::round,
+        Function::Sin => Value::sin,
+        Function::Cos => Value::cos,
+        Function::Tan => Value::tan,
+        Function::Sinh => Value::sinh,
+        Function::Cosh => Value::cosh,
+        Function::Tanh => Value::tanh,
+        Function::Asin => Value::asin,
+        Function::Acos => Value::acos,
+        Function::Atan => Value::atan,
+        Function::Asinh => Value::asinh,
+        Function::Acosh => Value::acosh,
+        Function::Atanh => Value::atanh,
+        Function::Rad => Value::rad,
+        Function::Deg => Value::deg,
+        Function::Sqrt => Value::sqrt,
+        Function::Cbrt => Value::cbrt,
+        Function::Lg => Value::lg,
+        Function::Ln => Value::ln,
+        Function::Exp => Value::exp,
+        Function::Re => Value::re,
+        Function::Im => Value::im,
+        Function::Arg => Value::arg,
+        Function::Conj => Value::conj,
+        _ => return None,
+    })
+}
+
+/// Whether a value is a real number other than an infinity or NaN.
+fn is_finite_real(value: &Value) -> bool {
+    match value {
+        Value::Float(n) => n.is_finite(),
+        Value::Complex(_) => false,
+        _ => true,
+    }
+}
+
+/// Check the result of an operation on `operands` for [`Backend::StrictFloat`].
+///
+/// If the operands are finite and real but the result is not, an infinite result is an overflow
+/// if `overflows`, and any other is outside the domain of the operation `name`.
+fn strict(
+    operands: &[Value],
+    result: Value,
+    name: &str,
+    overflows: bool,
+) -> Result<Value, ValueError> {
+    if is_finite_real(&result) || !operands.iter().all(is_finite_real) {
+        Ok(result)
+    } else if overflows && matches!(result, Value::Float(n) if n.is_infinite()) {
+        Err(ArithmeticError::Overflow.into())
+    } else {
+        Err(ValueError::Domain(name.to_owned()))
+    }
+}
+
+/// Apply a built-in function to real or complex arguments.
+fn evaluate(function: Function, args: Vec<Value>) -> Result<Value, ValueError> {
+    // complex numbers have no order
+    if matches!(function, Function::Min | Function::Max | Function::Clamp)
+        && args
+            .iter()
+            .any(|arg| matches!(arg, Value::Complex(n) if n.im != 0.0))
+    {
+        return Err(ValueError::ImproperlyComplex);
+    }
+    let mut args = args.into_iter();
+    let mut next = || args.next().expect("arity has been checked");
+    if let Some(function) = unary(function) {
+        return Ok(function(next()));
+    }
+    Ok(match function {
+        Function::Log => {
+            let x = next();
+            match args.next() {
+                Some(base) => x.log_base(base),
+                None => x.log(),
+            }
+        }
+        Function::Atan2 => next().atan2(next())?,
+        Function::Factorial => next().factorial()?,
+        Function::Hypot => next().hypot(next()),
+        Function::Min => {
+            let first = next();
+            args.fold(first, Value::min)
+        }
+        Function::Max => {
+            let first = next();
+            args.fold(first, Value::max)
+        }
+        // unlike `Ord::clamp`, an inverted range does not panic: `hi` wins
+        Function::Clamp => next().max(next()).min(next()),
+        _ => unreachable!("unary functions have already been evaluated"),
+    })
+}
+
+impl Calcable for Value {
+    fn parse(literal: &str, radix: u32, backend: Backend) -> Result<Self, ValueError> {
+        let value = match (radix, backend) {
+            (2, _) => Value::parse_binary(literal),
+            (8, _) => Value::parse_octal(literal),
+            (16, _) => Value::parse_hex(literal),
+            (_, Backend::Float | Backend::StrictFloat | Backend::Machine { .. }) => {
+                Value::parse_decimal(literal)
+            }
+            (_, Backend::Decimal { .. }) => Value::parse_decimal_exact(literal),
+        }?;
+        match backend.machine() {
+            Some(machine) => machine.literal(value, radix, literal),
+            None => Ok(value),
+        }
+    }
+
+    fn constant(constant: Constant) -> Result<Self, ValueError> {
+        Ok(match constant {
+            Constant::E => Value::E,
+            Constant::Pi => Value::PI,
+            Constant::I => Value::I,
+            Constant::Tau => Value::TAU,
+            Constant::Phi => Value::PHI,
+            Constant::Inf => Value::INFINITY,
+            Constant::Nan => Value::NAN,
+            Constant::Sqrt2 => Value::SQRT_2,
+            Constant::Ln2 => Value::LN_2,
+            // CODATA 2022 recommended values
+            Constant::SpeedOfLight => Value::UnsignedInt(299_792_458),
+            Constant::Gravitation => Value::Float(6.674_30e-11),
+            Constant::Planck => Value::Float(6.626_070_15e-34),
+            Constant::ReducedPlanck => Value::Float(1.054_571_817e-34),
+            Constant::Boltzmann => Value::Float(1.380_649e-23),
+            Constant::Avogadro => Value::Float(6.022_140_76e23),
+            Constant::ElementaryCharge => Value::Float(1.602_176_634e-19),
+            Constant::VacuumPermeability => Value::Float(1.256_637_061_27e-6),
+            Constant::VacuumPermittivity => Value::Float(8.854_187_818_8e-12),
+            Constant::ElectronMass => Value::Float(9.109_383_713_9e-31),
+            Constant::ProtonMass => Value::Float(1.672_621_925_95e-27),
+            Constant::GasConstant => Value::Float(8.314_462_618),
+            Constant::StefanBoltzmann => Value::Float(5.670_374_419e-8),
+            Constant::FineStructure => Value::Float(7.297_352_564_3e-3),
+        })
+    }
+
+    fn prefix(
+        operator: PrefixOperator,
+        operand: Self,
+        backend: Backend,
+    ) -> Result<Self, ValueError> {
+        if let Some(machine) = backend.machine() {
+            return machine.prefix(operator, operand);
+        }
+        match operator {
+            PrefixOperator::Negation => Ok(-operand),
+            PrefixOperator::Not => !operand,
+        }
+    }
+
+    fn infix(
+        operator: InfixOperator,
+        left: Self,
+        right: Self,
+        backend: Backend,
+    ) -> Result<Self, ValueError> {
+        if let Some(machine) = backend.machine() {
+            return machine.infix(operator, left, right);
+        }
+        let strict_float = backend == Backend::StrictFloat;
+        // dividing by an exact 0 is an error, as is dividing by a float 0 in strict mode
+        let divisor = match operator {
+            InfixOperator::Div | InfixOperator::TruncDiv | InfixOperator::Rem => Some(&right),
+            // `0**-n` is `1 / 0**n`
+            InfixOperator::Pow
+                if right.order() < Order::Complex && right < Value::UnsignedInt(0) =>
+            {
+                Some(&left)
+            }
+            _ => None,
+        };
+        if let Some(divisor) = divisor {
+            if (divisor.order() < Order::Float || strict_float) && *divisor == Value::UnsignedInt(0)
+            {
+                return Err(ArithmeticError::DivideBy0.into());
+            }
+        }
+        let operands = strict_float.then(|| [left.clone(), right.clone()]);
+        let result = match operator {
+            InfixOperator::Add => Ok(left + right),
+            InfixOperator::Sub => Ok(left - right),
+            InfixOperator::Mul => Ok(left * right),
+            InfixOperator::Div => Ok(left / right),
+            InfixOperator::TruncDiv => Ok(left.trunc_div(right)),
+            InfixOperator::Rem => Ok(left % right),
+            InfixOperator::Pow => left.pow(right),
+            InfixOperator::Lshift => left << right,
+            InfixOperator::Rshift => left >> right,
+            InfixOperator::RotateL => left.rotate_left(right),
+            InfixOperator::RotateR => left.rotate_right(right),
+            InfixOperator::BitAnd => left & right,
+            InfixOperator::BitOr => left | right,
+            InfixOperator::BitXor => left ^ right,
+            _ => unreachable!("comparisons and logical operators are not arithmetic"),
+        }?;
+        match operands {
+            Some(operands) => strict(&operands, result, operator.into(), true),
+            None => Ok(result),
+        }
+    }
+
+    fn function(function: Function, args: Vec<Self>, backend: Backend) -> Result<Self, ValueError> {
+        if function == Function::Unix {
+            return Err(ValueError::Unsupported("dates and times".into()));
+        }
+        if let Some(machine) = backend.machine() {
+            return machine.function(function, args);
+        }
+        if function.is_bitwise() {
+            return Value::bit_function(function, args, None);
+        }
+        let operands = (backend == Backend::StrictFloat).then(|| args.clone());
+        let result = evaluate(function, args)?;
+        match operands {
+            Some(operands) => {
+                let overflows = matches!(
+                    function,
+                    Function::Exp
+                        | Function::Sinh
+                        | Function::Cosh
+                        | Function::Hypot
+                        | Function::Deg
+                        | Function::Factorial
+                );
+                strict(&operands, result, function.into(), overflows)
+            }
+            None => Ok(result),
+        }
+    }
+
+    fn numeric_cmp(&self, other: &Self) -> Option<Ordering> {
+        Value::numeric_cmp(self, other)
+    }
+
+    fn is_truthy(&self) -> bool {
+        Value::is_truthy(self)
+    }
+
+    fn format(&self, format: &NumFmt) -> Result<String, num_runtime_fmt::Error> {
+        Value::format(self, format)
+    }
+
+    fn format_as(
+        &self,
+        format: &NumFmt,
+        ty: MachineType,
+    ) -> Result<String, num_runtime_fmt::Error> {
+        Value::format_as(self, format, ty)
+    }
+
+    fn format_polar(&self, format: &NumFmt) -> Result<String, num_runtime_fmt::Error> {
+        Value::format_polar(self, format)
+    }
+
+    fn cast(self, ty: MachineType) -> Result<Self, ValueError> {
+        ty.cast(&self)
+    }
+
+    fn lost_precision(operands: &[Self], result: &Self) -> bool {
+        Value::lost_precision(operands, result)
+    }
+
+    fn finish(self, backend: Backend) -> Self {
+        backend.round(self)
+    }
+}
@@ -4,8 +4,8 @@ use crate::{value::dispatch_operation, Value};
 
 impl Ord for Value {
     fn cmp(&self, other: &Self) -> Ordering {
-        let mut left = *self;
-        let mut right = *other;
+        let mut left = self.clone();
+        let mut right = other.clone();
         left.match_orders(&mut right);
 
         match (left, right) {
@@ -13,7 +13,15 @@ impl Ord for Value {
             (Value::UnsignedBigInt(l), Value::UnsignedBigInt(r)) => l.cmp(&r),
             (Value::SignedInt(l), Value::SignedInt(r)) => l.cmp(&r),
             (Value::SignedBigInt(l), Value::SignedBigInt(r)) => l.cmp(&r),
+            (Value::ArbitraryInt(l), Value::ArbitraryInt(r)) => l.cmp(&r),
+            (Value::Rational(l), Value::Rational(r)) => l.cmp(&r),
+            (Value::Decimal(l), Value::Decimal(r)) => l.cmp(&r),
             (Value::Float(l), Value::Float(r)) => l.total_cmp(&r),
+            // complex numbers have no natural order, so `min` and `max` reject them;
+            // for sorting, real part first is conventional
+            (Value::Complex(l), Value::Complex(r)) => {
+                l.re.total_cmp(&r.re).then(l.im.total_cmp(&r.im))
+            }
             _ => unreachable!("both sides have equal orders because we did `match_orders`"),
         }
     }
@@ -27,8 +35,8 @@ impl PartialOrd for Value {
 
 impl PartialEq for Value {
     fn eq(&self, other: &Self) -> bool {
-        let mut left = *self;
-        let mut right = *other;
+        let mut left = self.clone();
+        let mut right = other.clone();
         dispatch_operation!(left, right, l, |r| l == r)
     }
 }
@@ -37,22 +45,51 @@ impl Eq for Value {}
 
 impl Value {
     /// Perform a strict equality comparison: this is equal if the values have equal value and order _without promotion_.
-    pub fn strict_eq(self, other: Self) -> bool {
+    pub fn strict_eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Value::UnsignedInt(l), Value::UnsignedInt(r)) => l == r,
             (Value::UnsignedBigInt(l), Value::UnsignedBigInt(r)) => l == r,
             (Value::SignedInt(l), Value::SignedInt(r)) => l == r,
             (Value::SignedBigInt(l), Value::SignedBigInt(r)) => l == r,
+            (Value::ArbitraryInt(l), Value::ArbitraryInt(r)) => l == r,
+            (Value::Rational(l), Value::Rational(r)) => l == r,
+            (Value::Decimal(l), Value::Decimal(r)) => l == r,
             (Value::Float(l), Value::Float(r)) => l == r,
+            (Value::Complex(l), Value::Complex(r)) => l == r,
             _ => false,
         }
     }
 
+    /// Compare the logical values of `self` and `other` the way mathematical comparison operators do.
+    ///
+    /// Unlike [`Ord::cmp`], floats are compared according to IEEE 754:
+    /// `-0.0` equals `0.0`, and `NaN` is unordered with respect to everything.
+    /// Complex numbers with a nonzero imaginary part are likewise unordered.
+    pub fn numeric_cmp(&self, other: &Self) -> Option<Ordering> {
+        let mut left = self.clone();
+        let mut right = other.clone();
+        left.match_orders(&mut right);
+
+        match (&left, &right) {
+            (Value::Float(l), Value::Float(r)) => l.partial_cmp(r),
+            (Value::Complex(l), Value::Complex(r)) if l.im == 0.0 && r.im == 0.0 => {
+                l.re.partial_cmp(&r.re)
+            }
+            (Value::Complex(_), Value::Complex(_)) => None,
+            _ => Some(left.cmp(&right)),
+        }
+    }
+
+    /// Whether this value counts as true in a logical context: that is, whether it is nonzero.
+    pub fn is_truthy(&self) -> bool {
+        *self != Value::UnsignedInt(0)
+    }
+
     /// Compute a strict ordering: this orders first by the [Order][super::Order], then by value only if the orders match
-    pub fn strict_cmp(self, other: Self) -> Ordering {
+    pub fn strict_cmp(&self, other: &Self) -> Ordering {
         self.order()
             .cmp(&other.order())
-            .then_with(|| self.cmp(&other))
+            .then_with(|| self.cmp(other))
     }
 }
 
@@ -107,7 +144,7 @@ mod ordering_tests {
     fn strict_eq_same_variant_same_value() {
         let a: Value = 42_u64.into();
         let b: Value = 42_u64.into();
-        assert!(a.strict_eq(b));
+        assert!(a.strict_eq(&b));
     }
 
     #[test]
@@ -115,7 +152,7 @@ mod ordering_tests {
         let a: Value = 42_u64.into();
         let b: Value = 42_u128.into();
         // Same numeric value, but different variants
-        assert!(!a.strict_eq(b));
+        assert!(!a.strict_eq(&b));
     }
 
     // ---------- STRICT ORDERING ----------
@@ -123,7 +160,7 @@ mod ordering_tests {
     fn strict_cmp_same_variant() {
         let a: Value = 10_i64.into();
         let b: Value = 20_i64.into();
-        assert_eq!(a.strict_cmp(b), Ordering::Less);
+        assert_eq!(a.strict_cmp(&b), Ordering::Less);
     }
 
     #[test]
@@ -131,7 +168,7 @@ mod ordering_tests {
         let a: Value = 10_u64.into();
         let b: Value = 10_u128.into();
         // Different variants: strict_cmp should order by variant discriminant first
-        assert_eq!(a.strict_cmp(b), Ordering::Less);
+        assert_eq!(a.strict_cmp(&b), Ordering::Less);
     }
 
     // ---------- EDGE CASES ----------
@@ -149,10 +186,80 @@ mod ordering_tests {
         assert!(a < b);
     }
 
+    // ---------- NUMERIC COMPARISON ----------
+    #[rstest]
+    fn numeric_cmp_across_variants(
+        #[values(1_u64, 1_u128, 1_i64, 1_i128, 1.0_f64)] one: impl Into<Value>,
+        #[values(2_u64, 2_u128, 2_i64, 2_i128, 2.0_f64)] two: impl Into<Value>,
+    ) {
+        let one = one.into();
+        let two = two.into();
+        assert_eq!(one.numeric_cmp(&two), Some(Ordering::Less));
+        assert_eq!(two.numeric_cmp(&one), Some(Ordering::Greater));
+        assert_eq!(one.numeric_cmp(&one), Some(Ordering::Equal));
+    }
+
+    #[test]
+    fn numeric_cmp_signed_zeros_are_equal() {
+        let neg_zero: Value = (-0.0_f64).into();
+        let pos_zero: Value = 0.0_f64.into();
+        assert_eq!(neg_zero.numeric_cmp(&pos_zero), Some(Ordering::Equal));
+    }
+
+    #[test]
+    fn numeric_cmp_nan_is_unordered() {
+        let nan: Value = f64::NAN.into();
+        let one: Value = 1_u64.into();
+        assert_eq!(nan.numeric_cmp(&one), None);
+        assert_eq!(one.numeric_cmp(&nan), None);
+    }
+
+    // ---------- TRUTHINESS ----------
+    #[rstest]
+    fn zero_is_falsy(
+        #[values(0_u64, 0_u128, 0_i64, 0_i128, 0.0_f64, -0.0_f64)] zero: impl Into<Value>,
+    ) {
+        assert!(!zero.into().is_truthy());
+    }
+
+    #[rstest]
+    fn nonzero_is_truthy(
+        #[values(1_u64, 2_u128, -1_i64, -2_i128, 0.5_f64, f64::NAN)] val: impl Into<Value>,
+    ) {
+        assert!(val.into().is_truthy());
+    }
+
+    #[test]
+    fn arbitrary_compares_with_every_order() {
+        let big: Value = (num_bigint::BigInt::from(u128::MAX) + 1_u64).into();
+        assert!(big > Value::from(u128::MAX));
+        assert!(-big.clone() < Value::from(i128::MIN));
+        assert!(big < Value::from(f64::INFINITY));
+        assert_eq!(big, Value::from(2.0_f64.powi(128)));
+    }
+
+    #[test]
+    fn fractions_compare_exactly() {
+        let third = Value::from(1_u64) / 3_u64;
+        assert!(third < Value::from(1_u64) / 2_u64);
+        assert!(third > Value::from(0_u64));
+        assert_eq!(Value::from(2_u64) / 6_u64, third);
+    }
+
     #[test]
     fn ordering_float_vs_int() {
         let a: Value = 3.5_f64.into();
         let b: Value = 4_i64.into();
         assert!(a < b);
     }
+
+    #[test]
+    fn complex_numbers_are_unordered() {
+        let i = Value::I;
+        assert_eq!(i.numeric_cmp(&Value::from(1_u64)), None);
+        assert_eq!(Value::from(1_u64).numeric_cmp(&i), None);
+        assert_eq!(i.numeric_cmp(&Value::I), None);
+        assert_eq!(i, Value::I);
+        assert_ne!(i, Value::from(1_u64));
+    }
 }
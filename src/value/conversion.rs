@@ -10,6 +10,7 @@ macro_rules! impl_from {
     };
 }
 
+impl_from!(bool => UnsignedInt);
 impl_from!(u8 => UnsignedInt);
 impl_from!(u16 => UnsignedInt);
 impl_from!(u32 => UnsignedInt);
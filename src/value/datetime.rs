@@ -0,0 +1,323 @@
+//! Dates, times of day, and durations.
+//!
+//! Dates are proleptic Gregorian, and all times are UTC. Instants are handled as a number of
+//! nanoseconds since the Unix epoch, so that they can be converted exactly to and from [`Value`].
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+
+use super::Value;
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+const SECONDS_PER_DAY: i128 = 86_400;
+
+/// The units a duration literal may be written in, and their lengths in seconds.
+const DURATION_UNITS: [(&str, i128); 4] =
+    [("d", SECONDS_PER_DAY), ("h", 3600), ("m", 60), ("s", 1)];
+
+/// The number of days from the epoch to a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month_from_march = i64::from((month + 9) % 12);
+    let day_of_year = (153 * month_from_march + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The date which is `days` days from the epoch, as `(year, month, day)`,
+/// if its year fits in an `i64`.
+fn civil_from_days(days: i64) -> Option<(i64, u32, u32)> {
+    let days = days.checked_add(719_468)?;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u32;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    Some((year, month, day))
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// The number of nanoseconds a string of decimal digits after the point stands for,
+/// in a unit of `unit` nanoseconds. Digits beyond the nanosecond are ignored.
+fn fraction_nanos(digits: &str, unit: i128) -> Option<i128> {
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let digits = &digits[..digits.len().min(18)];
+    let numer: i128 = if digits.is_empty() {
+        0
+    } else {
+        digits.parse().ok()?
+    };
+    Some(numer * unit / 10_i128.pow(digits.len() as u32))
+}
+
+/// The number of nanoseconds since midnight of a time of day such as `14:30` or `14:30:05.5`.
+fn parse_time_of_day(s: &str) -> Option<i128> {
+    let mut parts = s.splitn(3, ':');
+    let hours: i128 = parts.next()?.parse().ok()?;
+    let minutes: i128 = parts.next()?.parse().ok()?;
+    let (seconds, fraction) = match parts.next() {
+        Some(seconds) => seconds.split_once('.').unwrap_or((seconds, "")),
+        None => ("0", ""),
+    };
+    let seconds: i128 = seconds.parse().ok()?;
+    if hours > 23 || minutes > 59 || seconds > 59 {
+        return None;
+    }
+    let seconds = hours * 3600 + minutes * 60 + seconds;
+    Some(seconds * NANOS_PER_SECOND + fraction_nanos(fraction, NANOS_PER_SECOND)?)
+}
+
+/// Parse a date such as `2026-10-17`, optionally followed by a time of day
+/// such as `T14:30` or ` 14:30:05Z`.
+pub(crate) fn parse_datetime(s: &str) -> Option<SystemTime> {
+    let s = s.strip_suffix('Z').unwrap_or(s);
+    let (date, time) = match s.split_once(['T', ' ']) {
+        Some((date, time)) => (date, parse_time_of_day(time)?),
+        None => (s, 0),
+    };
+    let mut parts = date.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: u32 = parts.next()?.parse().ok()?;
+    let day: u32 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+    let days = i128::from(days_from_civil(year, month, day));
+    from_nanos(days * SECONDS_PER_DAY * NANOS_PER_SECOND + time)
+}
+
+/// Parse a duration such as `3h 20m` or `1.5s`, or a time of day such as `T14:30` or `14:30:05`,
+/// which is the duration since midnight.
+pub(crate) fn parse_duration(s: &str) -> Option<Duration> {
+    let nanos = if s.contains(':') {
+        parse_time_of_day(s.strip_prefix('T').unwrap_or(s))?
+    } else {
+        s.split_whitespace().try_fold(0, |total, component| {
+            let (number, unit) = component.split_at(component.find(|c: char| c.is_alphabetic())?);
+            let unit = match unit {
+                "ms" => NANOS_PER_SECOND / 1000,
+                _ => {
+                    let (_, seconds) = DURATION_UNITS.iter().find(|(symbol, _)| *symbol == unit)?;
+                    seconds * NANOS_PER_SECOND
+                }
+            };
+            let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+            let whole: i128 = whole.parse().ok()?;
+            let nanos = whole
+                .checked_mul(unit)?
+                .checked_add(fraction_nanos(fraction, unit)?)?;
+            nanos.checked_add(total)
+        })?
+    };
+    let seconds = u64::try_from(nanos / NANOS_PER_SECOND).ok()?;
+    Some(Duration::new(seconds, (nanos % NANOS_PER_SECOND) as u32))
+}
+
+/// The number of nanoseconds from the epoch to `time`.
+pub(crate) fn to_nanos(time: SystemTime) -> i128 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(since) => since.as_nanos() as i128,
+        Err(err) => -(err.duration().as_nanos() as i128),
+    }
+}
+
+/// The instant `nanos` nanoseconds from the epoch, if the system can represent it.
+fn from_nanos(nanos: i128) -> Option<SystemTime> {
+    let magnitude = nanos.unsigned_abs();
+    let duration = Duration::new(
+        u64::try_from(magnitude / NANOS_PER_SECOND as u128).ok()?,
+        (magnitude % NANOS_PER_SECOND as u128) as u32,
+    );
+    if nanos < 0 {
+        UNIX_EPOCH.checked_sub(duration)
+    } else {
+        UNIX_EPOCH.checked_add(duration)
+    }
+}
+
+/// Round `time` down to a multiple of `step` seconds since the epoch.
+fn floor(time: SystemTime, step: i128) -> SystemTime {
+    let step = step * NANOS_PER_SECOND;
+    from_nanos(to_nanos(time).div_euclid(step) * step).unwrap_or(time)
+}
+
+/// `time`, without its fraction of a second.
+pub(crate) fn whole_seconds(time: SystemTime) -> SystemTime {
+    floor(time, 1)
+}
+
+/// The start of the day containing `time`.
+pub(crate) fn midnight(time: SystemTime) -> SystemTime {
+    floor(time, SECONDS_PER_DAY)
+}
+
+/// A number of nanoseconds as a number of seconds, exactly.
+pub(crate) fn nanos_to_seconds(nanos: i128) -> Value {
+    BigRational::new(BigInt::from(nanos), BigInt::from(NANOS_PER_SECOND)).into()
+}
+
+/// A number of seconds as a whole number of nanoseconds, if it fits.
+pub(crate) fn seconds_to_nanos(seconds: &Value) -> Option<i128> {
+    (seconds.clone() * Value::from(NANOS_PER_SECOND))
+        .round()
+        .to_i128()
+}
+
+/// Render a fraction of a second, without trailing zeros: `.5`.
+fn format_fraction(nanos: i128) -> String {
+    if nanos == 0 {
+        return String::new();
+    }
+    let digits = format!("{nanos:09}");
+    format!(".{}", digits.trim_end_matches('0'))
+}
+
+/// Render the instant `nanos` nanoseconds from the epoch as `2026-10-17T14:30:00`,
+/// or as `2026-10-17` if it is midnight.
+///
+/// Returns `None` if the date is too far from the epoch to have a year.
+pub(crate) fn format_datetime(nanos: i128) -> Option<String> {
+    let seconds = nanos.div_euclid(NANOS_PER_SECOND);
+    let days = i64::try_from(seconds.div_euclid(SECONDS_PER_DAY)).ok()?;
+    let (year, month, day) = civil_from_days(days)?;
+    let date = format!("{year:04}-{month:02}-{day:02}");
+    let time = seconds.rem_euclid(SECONDS_PER_DAY);
+    let fraction = nanos.rem_euclid(NANOS_PER_SECOND);
+    if time == 0 && fraction == 0 {
+        return Some(date);
+    }
+    Some(format!(
+        "{date}T{:02}:{:02}:{:02}{}",
+        time / 3600,
+        time / 60 % 60,
+        time % 60,
+        format_fraction(fraction),
+    ))
+}
+
+/// Render a duration of `nanos` nanoseconds in the form of a literal, such as `1d 3h 20m 5.5s`.
+pub(crate) fn format_duration(nanos: i128) -> String {
+    let sign = if nanos < 0 { "-" } else { "" };
+    let nanos = nanos.abs();
+    let mut seconds = nanos / NANOS_PER_SECOND;
+    let fraction = nanos % NANOS_PER_SECOND;
+    let mut components = Vec::new();
+    for (symbol, length) in DURATION_UNITS {
+        let count = seconds / length;
+        seconds %= length;
+        if symbol == "s" && (count != 0 || fraction != 0 || components.is_empty()) {
+            components.push(format!("{count}{}s", format_fraction(fraction)));
+        } else if count != 0 {
+            components.push(format!("{count}{symbol}"));
+        }
+    }
+    format!("{sign}{}", components.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(1970, 1, 1, 0)]
+    #[case(2000, 3, 1, 11_017)]
+    #[case(2026, 10, 17, 20_743)]
+    #[case(1969, 12, 31, -1)]
+    #[case(1600, 2, 29, -135_081)]
+    fn civil_days(#[case] year: i64, #[case] month: u32, #[case] day: u32, #[case] days: i64) {
+        assert_eq!(days_from_civil(year, month, day), days);
+        assert_eq!(civil_from_days(days), Some((year, month, day)));
+    }
+
+    #[test]
+    fn days_beyond_range() {
+        assert_eq!(civil_from_days(i64::MAX), None);
+        let days = i128::from(i64::MAX - 7);
+        assert_eq!(
+            format_datetime(days * SECONDS_PER_DAY * NANOS_PER_SECOND),
+            None
+        );
+    }
+
+    #[rstest]
+    #[case("2026-10-17", "2026-10-17")]
+    #[case("2026-10-17T14:30", "2026-10-17T14:30:00")]
+    #[case("2026-10-17 14:30:05.250Z", "2026-10-17T14:30:05.25")]
+    #[case("1969-07-20T20:17", "1969-07-20T20:17:00")]
+    #[case("2024-02-29", "2024-02-29")]
+    fn datetime_roundtrip(#[case] input: &str, #[case] expect: &str) {
+        let nanos = to_nanos(parse_datetime(input).unwrap());
+        assert_eq!(format_datetime(nanos).unwrap(), expect);
+    }
+
+    #[rstest]
+    fn invalid_datetime(
+        #[values(
+            "2026-13-01",
+            "2026-02-29",
+            "2026-10-00",
+            "2026-10-17T24:00",
+            "2026-10-17T12:60"
+        )]
+        input: &str,
+    ) {
+        assert_eq!(parse_datetime(input), None);
+    }
+
+    #[rstest]
+    #[case("3h 20m", 12_000, 0, "3h 20m")]
+    #[case("1.5s", 1, 500_000_000, "1.5s")]
+    #[case("250ms", 0, 250_000_000, "0.25s")]
+    #[case("2d 1s", 172_801, 0, "2d 1s")]
+    #[case("0d", 0, 0, "0s")]
+    #[case("T14:30", 52_200, 0, "14h 30m")]
+    #[case("09:15:30.5", 33_330, 500_000_000, "9h 15m 30.5s")]
+    fn duration(#[case] input: &str, #[case] secs: u64, #[case] nanos: u32, #[case] expect: &str) {
+        let duration = parse_duration(input).unwrap();
+        assert_eq!(duration, Duration::new(secs, nanos));
+        assert_eq!(format_duration(duration.as_nanos() as i128), expect);
+    }
+
+    #[test]
+    fn negative_duration() {
+        assert_eq!(format_duration(-90 * NANOS_PER_SECOND), "-1m 30s");
+    }
+
+    #[test]
+    fn truncation() {
+        let time = parse_datetime("2026-10-17T14:30:05.75").unwrap();
+        assert_eq!(
+            whole_seconds(time),
+            parse_datetime("2026-10-17T14:30:05").unwrap()
+        );
+        assert_eq!(midnight(time), parse_datetime("2026-10-17").unwrap());
+        let before_epoch = parse_datetime("1969-12-31T23:00").unwrap();
+        assert_eq!(
+            midnight(before_epoch),
+            parse_datetime("1969-12-31").unwrap()
+        );
+    }
+}
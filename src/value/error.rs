@@ -1,4 +1,5 @@
-use crate::ast::HistoryIndexKind;
+use super::{machine::MachineType, units::Dimension};
+use crate::ast::{Arity, HistoryIndexKind};
 
 #[derive(Debug, thiserror::Error)]
 pub enum ArithmeticError {
@@ -18,8 +19,46 @@ pub enum Error {
     Parse(#[from] ParseValueError),
     #[error("{0:?} history index {1} out of bounds: [0..{2})")]
     HistoryOOB(HistoryIndexKind, usize, usize),
+    #[error("unknown variable \"{0}\"")]
+    UnknownVariable(String),
+    #[error("unknown function \"{0}\"")]
+    UnknownFunction(String),
+    #[error("function \"{0}\" takes {1} but got {2}")]
+    Arity(String, Arity, usize),
+    #[error("{0} is not supported by this numeric type")]
+    Unsupported(String),
+    #[error("function calls nested more than {0} deep")]
+    RecursionLimit(usize),
+    #[error("incompatible dimensions: {0} and {1}")]
+    DimensionMismatch(Dimension, Dimension),
+    #[error("a quantity in {0} has no power with that exponent")]
+    FractionalDimension(Dimension),
+    #[error("{0} has no finite real result for these arguments")]
+    Domain(String),
+    #[error("result is not exact: an operand was rounded to a float")]
+    Inexact,
+    #[error("a width of {0} bits is not supported")]
+    Width(u32),
+    #[error("bits [{0}:{1}] are not a range: the high bit comes first")]
+    BitRange(u32, u32),
+    #[error("literal {0} is out of range for {1}")]
+    LiteralRange(String, MachineType),
+    #[error("an operand of type {0} cannot be combined with one of type {1}")]
+    TypeMismatch(MachineType, MachineType),
+    #[error("\"{0}\" is not a valid date or time")]
+    InvalidDate(String),
+    #[error("dates can only be compared, subtracted from one another, or offset by a time")]
+    DateArithmetic,
     #[error("attempted to perform an operation which only makes sense for integers, but value is currently a float")]
     ImproperlyFloat,
+    #[error("attempted to perform an operation which only makes sense for integers, but value is currently a fraction")]
+    ImproperlyRational,
+    #[error("attempted to perform an operation which only makes sense for integers, but value is currently a decimal")]
+    ImproperlyDecimal,
+    #[error("attempted to perform an operation which only makes sense for real numbers, but value is currently a complex number")]
+    ImproperlyComplex,
+    #[error("attempted to perform an operation which only makes sense for fixed-width integers, but value is an arbitrary-precision integer")]
+    Unbounded,
 }
 
 #[derive(Debug, thiserror::Error)]
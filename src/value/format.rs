@@ -1,6 +1,132 @@
-use num_runtime_fmt::Numeric;
+use std::fmt;
 
-use crate::Value;
+use num_bigint::{BigInt, BigUint, Sign};
+use num_complex::Complex64;
+use num_rational::BigRational;
+use num_runtime_fmt::{Base, NumFmt, Numeric};
+
+use crate::{MachineType, Value};
+
+/// How many digits of a repeating decimal expansion to render.
+const FRACTION_DIGITS: usize = 32;
+
+/// The digits of the magnitude of `n` in the given radix, least significant first.
+fn least_significant_first(n: &BigInt, radix: u32) -> Box<dyn Iterator<Item = char>> {
+    let digits: Vec<char> = n.magnitude().to_str_radix(radix).chars().collect();
+    Box::new(digits.into_iter().rev())
+}
+
+/// The digits after the decimal point of the magnitude of `n`, most significant first.
+///
+/// Digits stop when the expansion terminates, or after [`FRACTION_DIGITS`] digits if it repeats.
+fn fraction_digits(n: &BigRational) -> Box<dyn Iterator<Item = char>> {
+    let denom = n.denom().clone();
+    let mut remainder = n.numer().magnitude() % denom.magnitude();
+    let mut digits = Vec::new();
+    while remainder != BigUint::ZERO && digits.len() < FRACTION_DIGITS {
+        remainder *= 10_u8;
+        let digit = &remainder / denom.magnitude();
+        remainder %= denom.magnitude();
+        digits
+            .push(char::from_digit(digit.try_into().expect("digit < 10"), 10).expect("digit < 10"));
+    }
+    Box::new(digits.into_iter())
+}
+
+/// Render a float according to `format`.
+///
+/// The formatter only renders digits, so infinities and NaN are written as `inf`, `-inf`, and `NaN`.
+fn float<F>(n: F, format: &NumFmt) -> Result<String, num_runtime_fmt::Error>
+where
+    F: num_traits::Float + Numeric + fmt::Display,
+{
+    if n.is_finite() {
+        format.fmt(n)
+    } else {
+        Ok(n.to_string())
+    }
+}
+
+/// Join the rendered parts of a complex number as `a+bi`, or as `bi` when the real part is 0.
+///
+/// An imaginary part of magnitude 1 is written as a bare `i`.
+fn rectangular<E>(
+    n: &Complex64,
+    mut render: impl FnMut(f64) -> Result<String, E>,
+) -> Result<String, E> {
+    let im = if n.im.abs() == 1.0 {
+        String::new()
+    } else {
+        render(n.im.abs())?
+    };
+    if n.re == 0.0 {
+        let sign = if n.im.is_sign_negative() { "-" } else { "" };
+        return Ok(format!("{sign}{im}i"));
+    }
+    let sign = if n.im.is_sign_negative() { '-' } else { '+' };
+    Ok(format!("{}{sign}{im}i", render(n.re)?))
+}
+
+/// Display a complex number in rectangular form: `3+4i`.
+pub(crate) struct Rectangular<'a>(pub &'a Complex64);
+
+impl fmt::Display for Rectangular<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rendered = rectangular(self.0, |part| Ok::<_, fmt::Error>(part.to_string()))?;
+        f.write_str(&rendered)
+    }
+}
+
+impl Value {
+    /// Render this value according to `format`.
+    ///
+    /// Complex numbers are rendered in rectangular form, each part according to `format`.
+    /// Fractions are rendered as `numerator/denominator` by the default format, and otherwise
+    /// as decimals.
+    pub fn format(&self, format: &NumFmt) -> Result<String, num_runtime_fmt::Error> {
+        match self {
+            Value::Rational(n) if *format == NumFmt::default() => Ok(n.to_string()),
+            Value::Complex(n) => rectangular(n, |part| float(part, format)),
+            Value::Float(n) => float(*n, format),
+            _ => format.fmt(self.clone()),
+        }
+    }
+
+    /// Render a result of the machine type `ty` according to `format`.
+    ///
+    /// An `f32` is rendered with the digits which identify it as an `f32`, rather than
+    /// those of the `f64` which holds it: `0.1f32` is `0.1`, not `0.10000000149011612`.
+    /// In binary, octal, and hex, a negative integer is rendered as its bits: `-1i8 :x` is `ff`.
+    pub fn format_as(
+        &self,
+        format: &NumFmt,
+        ty: MachineType,
+    ) -> Result<String, num_runtime_fmt::Error> {
+        match self {
+            Value::Float(n) if ty == MachineType::F32 => float(*n as f32, format),
+            // other bases show a negative machine integer as its two's complement bits
+            _ if format.base() != Base::Decimal && Numeric::is_negative(self) => {
+                match ty.bit_pattern(self) {
+                    Some(pattern) => pattern.format(format),
+                    None => self.format(format),
+                }
+            }
+            _ => self.format(format),
+        }
+    }
+
+    /// Render this value in polar form, as `r*e**(θi)`, with each part according to `format`.
+    ///
+    /// This is valid input: evaluating the output reproduces the value, up to rounding.
+    pub fn format_polar(&self, format: &NumFmt) -> Result<String, num_runtime_fmt::Error> {
+        let (r, theta) = self.clone().promote_to_complex().to_polar();
+        Ok(format!(
+            "{}*e**({}i)",
+            float(r, format)?,
+            float(theta, format)?
+        ))
+    }
+}
 
 impl Numeric for Value {
     type BinIter = Box<dyn Iterator<Item = char>>;
@@ -13,8 +139,10 @@ impl Numeric for Value {
         match self {
             Value::UnsignedInt(n) => n.binary().map(|iter| Box::new(iter) as _),
             Value::UnsignedBigInt(n) => n.binary().map(|iter| Box::new(iter) as _),
-            Value::SignedInt(n) => n.binary().map(|iter| Box::new(iter) as _),
-            Value::SignedBigInt(n) => n.binary().map(|iter| Box::new(iter) as _),
+            Value::SignedInt(n) => n.unsigned_abs().binary().map(|iter| Box::new(iter) as _),
+            Value::SignedBigInt(n) => n.unsigned_abs().binary().map(|iter| Box::new(iter) as _),
+            Value::ArbitraryInt(n) => Some(least_significant_first(n, 2)),
+            Value::Rational(_) | Value::Decimal(_) | Value::Complex(_) => None,
             Value::Float(n) => n.binary().map(|iter| Box::new(iter) as _),
         }
     }
@@ -23,8 +151,10 @@ impl Numeric for Value {
         match self {
             Value::UnsignedInt(n) => n.octal().map(|iter| Box::new(iter) as _),
             Value::UnsignedBigInt(n) => n.octal().map(|iter| Box::new(iter) as _),
-            Value::SignedInt(n) => n.octal().map(|iter| Box::new(iter) as _),
-            Value::SignedBigInt(n) => n.octal().map(|iter| Box::new(iter) as _),
+            Value::SignedInt(n) => n.unsigned_abs().octal().map(|iter| Box::new(iter) as _),
+            Value::SignedBigInt(n) => n.unsigned_abs().octal().map(|iter| Box::new(iter) as _),
+            Value::ArbitraryInt(n) => Some(least_significant_first(n, 8)),
+            Value::Rational(_) | Value::Decimal(_) | Value::Complex(_) => None,
             Value::Float(n) => n.octal().map(|iter| Box::new(iter) as _),
         }
     }
@@ -44,23 +174,40 @@ impl Numeric for Value {
                 (l, r)
             }
             Value::SignedInt(n) => {
-                let (l, r) = n.decimal();
+                let (l, r) = n.unsigned_abs().decimal();
                 let l = Box::new(l) as _;
                 let r = r.map(|iter| Box::new(iter) as _);
                 (l, r)
             }
             Value::SignedBigInt(n) => {
-                let (l, r) = n.decimal();
+                let (l, r) = n.unsigned_abs().decimal();
                 let l = Box::new(l) as _;
                 let r = r.map(|iter| Box::new(iter) as _);
                 (l, r)
             }
+            Value::ArbitraryInt(n) => (least_significant_first(n, 10), None),
+            Value::Rational(n) => (
+                least_significant_first(&n.trunc().to_integer(), 10),
+                Some(fraction_digits(n)),
+            ),
+            Value::Decimal(n) => {
+                let digits = n.abs().to_string();
+                let (left, right) = digits.split_once('.').unwrap_or((&digits, ""));
+                let left: Vec<char> = left.chars().rev().collect();
+                let right: Vec<char> = right.chars().collect();
+                (
+                    Box::new(left.into_iter()) as _,
+                    (!right.is_empty()).then(|| Box::new(right.into_iter()) as _),
+                )
+            }
             Value::Float(n) => {
                 let (l, r) = n.decimal();
                 let l = Box::new(l) as _;
                 let r = r.map(|iter| Box::new(iter) as _);
                 (l, r)
             }
+            // only the real part has digits; `Value::format` renders both parts
+            Value::Complex(n) => Value::Float(n.re).decimal(),
         }
     }
 
@@ -68,8 +215,10 @@ impl Numeric for Value {
         match self {
             Value::UnsignedInt(n) => n.hex().map(|iter| Box::new(iter) as _),
             Value::UnsignedBigInt(n) => n.hex().map(|iter| Box::new(iter) as _),
-            Value::SignedInt(n) => n.hex().map(|iter| Box::new(iter) as _),
-            Value::SignedBigInt(n) => n.hex().map(|iter| Box::new(iter) as _),
+            Value::SignedInt(n) => n.unsigned_abs().hex().map(|iter| Box::new(iter) as _),
+            Value::SignedBigInt(n) => n.unsigned_abs().hex().map(|iter| Box::new(iter) as _),
+            Value::ArbitraryInt(n) => Some(least_significant_first(n, 16)),
+            Value::Rational(_) | Value::Decimal(_) | Value::Complex(_) => None,
             Value::Float(n) => n.hex().map(|iter| Box::new(iter) as _),
         }
     }
@@ -79,7 +228,76 @@ impl Numeric for Value {
             Value::UnsignedInt(_) | Value::UnsignedBigInt(_) => false,
             Value::SignedInt(n) => n.is_negative(),
             Value::SignedBigInt(n) => n.is_negative(),
+            Value::ArbitraryInt(n) => n.sign() == Sign::Minus,
+            Value::Rational(n) => n.numer().sign() == Sign::Minus,
+            Value::Decimal(n) => n.is_sign_negative(),
             Value::Float(n) => n.is_sign_negative(),
+            Value::Complex(n) => n.re.is_sign_negative(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(1, 8, "0", "125")]
+    #[case(-7, 2, "3", "5")]
+    #[case(22, 7, "3", "14285714285714285714285714285714")]
+    fn fraction_decimal_digits(
+        #[case] numer: i64,
+        #[case] denom: i64,
+        #[case] left: &str,
+        #[case] right: &str,
+    ) {
+        let value = Value::from(numer) / denom;
+        let (l, r) = value.decimal();
+        let l: String = l.collect::<Vec<_>>().into_iter().rev().collect();
+        assert_eq!(l, left);
+        assert_eq!(
+            r.expect("fractions have a fractional part")
+                .collect::<String>(),
+            right
+        );
+        assert_eq!(Numeric::is_negative(&value), numer < 0);
+    }
+
+    #[rstest]
+    #[case("", "-7/2")]
+    #[case(".2", "-3.50")]
+    #[case(",", "-3.5")]
+    fn fraction_format(#[case] format: &str, #[case] expect: &str) {
+        let value = Value::from(-7_i64) / 2_i64;
+        assert_eq!(value.format(&format.parse().unwrap()).unwrap(), expect);
+    }
+
+    #[rstest]
+    #[case(f64::INFINITY, "inf")]
+    #[case(f64::NEG_INFINITY, "-inf")]
+    #[case(f64::NAN, "NaN")]
+    fn non_finite_floats(#[case] n: f64, #[case] expect: &str) {
+        let value = Value::from(n);
+        assert_eq!(value.format(&NumFmt::default()).unwrap(), expect);
+        let format = "x".parse().unwrap();
+        assert_eq!(value.format(&format).unwrap(), expect);
+        let complex = Value::from(Complex64::new(1.0, n));
+        let sign = if expect.starts_with('-') { "" } else { "+" };
+        assert_eq!(
+            complex.format(&NumFmt::default()).unwrap(),
+            format!("1{sign}{expect}i")
+        );
+    }
+
+    #[rstest]
+    #[case(3.0, 4.0, "3+4i")]
+    #[case(3.0, -4.0, "3-4i")]
+    #[case(0.0, 2.0, "2i")]
+    #[case(0.0, -1.0, "-i")]
+    #[case(-0.5, 1.0, "-0.5+i")]
+    fn complex_rectangular_form(#[case] re: f64, #[case] im: f64, #[case] expect: &str) {
+        let value = Value::from(Complex64::new(re, im));
+        assert_eq!(value.to_string(), expect);
+    }
+}
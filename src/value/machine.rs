@@ -0,0 +1,640 @@
+//! Fixed-width machine types, to which [`Backend::Machine`][crate::Backend::Machine] pins all
+//! arithmetic.
+//!
+//! Integers are handled as [`BigInt`]s, so that each operation can compute its exact result
+//! before fitting it to the type according to the [`Overflow`] behavior. Bitwise operations
+//! work on the two's complement bit pattern of the type's width, and never overflow.
+
+use num_bigint::BigInt;
+use num_traits::{Euclid as _, One as _, Pow as _, Signed as _, ToPrimitive as _, Zero as _};
+
+use super::{ArithmeticError, Error, Result, Value};
+use crate::ast::{Function, InfixOperator, PrefixOperator};
+
+/// A fixed-width number type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum MachineType {
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    F32,
+    F64,
+}
+
+/// What happens when the result of integer arithmetic does not fit its type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum Overflow {
+    /// Keep the low bits of the result, as two's complement hardware does.
+    #[default]
+    Wrap,
+    /// Clamp the result to the least or greatest value of the type.
+    Saturate,
+    /// Fail with [`ArithmeticError::Overflow`].
+    Error,
+}
+
+impl MachineType {
+    /// The width of this type in bits.
+    pub fn bits(self) -> u32 {
+        match self {
+            Self::U8 | Self::I8 => 8,
+            Self::U16 | Self::I16 => 16,
+            Self::U32 | Self::I32 | Self::F32 => 32,
+            Self::U64 | Self::I64 | Self::F64 => 64,
+            Self::U128 | Self::I128 => 128,
+        }
+    }
+
+    /// Whether this is a floating-point type.
+    pub fn is_float(self) -> bool {
+        matches!(self, Self::F32 | Self::F64)
+    }
+
+    fn is_signed(self) -> bool {
+        matches!(
+            self,
+            Self::I8 | Self::I16 | Self::I32 | Self::I64 | Self::I128
+        )
+    }
+
+    /// The unsigned integer type of the same width, which holds each bit pattern of this type.
+    pub fn unsigned(self) -> Self {
+        match self {
+            Self::I8 => Self::U8,
+            Self::I16 => Self::U16,
+            Self::I32 | Self::F32 => Self::U32,
+            Self::I64 | Self::F64 => Self::U64,
+            Self::I128 => Self::U128,
+            ty => ty,
+        }
+    }
+
+    /// `2**bits`: the number of distinct values of this integer type.
+    fn modulus(self) -> BigInt {
+        BigInt::one() << self.bits()
+    }
+
+    fn min(self) -> BigInt {
+        if self.is_signed() {
+            -(BigInt::one() << (self.bits() - 1))
+        } else {
+            BigInt::zero()
+        }
+    }
+
+    fn max(self) -> BigInt {
+        if self.is_signed() {
+            (BigInt::one() << (self.bits() - 1)) - 1
+        } else {
+            self.modulus() - 1
+        }
+    }
+
+    /// The bit pattern of `n`, as an unsigned number less than the modulus.
+    fn pattern(self, n: &BigInt) -> BigInt {
+        n.rem_euclid(&self.modulus())
+    }
+
+    /// The bit pattern of an integer `value` of this type, as an unsigned number.
+    ///
+    /// Returns `None` for floats and non-integer values.
+    pub(crate) fn bit_pattern(self, value: &Value) -> Option<Value> {
+        if self.is_float() {
+            return None;
+        }
+        integer(value)
+            .ok()
+            .map(|n| Value::narrowest(self.pattern(&n)))
+    }
+
+    /// The value of this type which has the low bits of `n`.
+    fn wrap(self, n: &BigInt) -> BigInt {
+        let n = self.pattern(n);
+        if n > self.max() {
+            n - self.modulus()
+        } else {
+            n
+        }
+    }
+
+    /// Round a float to the precision of this type.
+    fn float(self, n: f64) -> Value {
+        match self {
+            Self::F32 => Value::Float(n as f32 as f64),
+            _ => Value::Float(n),
+        }
+    }
+
+    /// Convert `value` to this type, as `value as u8` does in Rust.
+    ///
+    /// Integers keep the low bits of their two's complement representation, so a cast to a
+    /// narrower type truncates them and a cast of a negative number to a wider type sign-extends it:
+    /// `0x1ff as u8` is `0xff` and `-1 as u16` is `0xffff`. Other numbers are truncated towards 0
+    /// and saturate at the bounds of the type: `300.7 as u8` is `255`, and NaN becomes `0`.
+    ///
+    /// The result is in the [`Value`] order which corresponds to this type, so `5 as i64` is
+    /// signed and `!(5 as i64)` is `-6`.
+    pub(crate) fn cast(self, value: &Value) -> Result {
+        if self.is_float() {
+            return Ok(self.float(float(value)));
+        }
+        let saturate = |n: BigInt| n.clamp(self.min(), self.max());
+        let n = match value {
+            Value::Rational(n) => saturate(n.trunc().to_integer()),
+            Value::Decimal(n) => saturate(BigInt::from(
+                n.trunc()
+                    .to_i128()
+                    .expect("decimals are narrower than i128"),
+            )),
+            Value::Complex(n) if n.im != 0.0 => return Err(Error::ImproperlyComplex),
+            Value::Float(_) | Value::Complex(_) => {
+                let n = float(value);
+                if n.is_nan() {
+                    BigInt::zero()
+                } else if n.is_infinite() {
+                    if n > 0.0 {
+                        self.max()
+                    } else {
+                        self.min()
+                    }
+                } else {
+                    saturate(
+                        num_traits::FromPrimitive::from_f64(n.trunc())
+                            .expect("finite floats convert to BigInt"),
+                    )
+                }
+            }
+            value => self.wrap(&integer(value)?),
+        };
+        Ok(match self {
+            Self::U8 | Self::U16 | Self::U32 | Self::U64 => {
+                Value::UnsignedInt(n.to_u64().expect("n fits this type"))
+            }
+            Self::U128 => Value::UnsignedBigInt(n.to_u128().expect("n fits this type")),
+            Self::I8 | Self::I16 | Self::I32 | Self::I64 => {
+                Value::SignedInt(n.to_i64().expect("n fits this type"))
+            }
+            Self::I128 => Value::SignedBigInt(n.to_i128().expect("n fits this type")),
+            Self::F32 | Self::F64 => unreachable!("floats have already been cast"),
+        })
+    }
+}
+
+/// The exact integer value of `value`, or an error if it has a fractional part.
+fn integer(value: &Value) -> Result<BigInt> {
+    match value {
+        Value::UnsignedInt(n) => Ok(BigInt::from(*n)),
+        Value::UnsignedBigInt(n) => Ok(BigInt::from(*n)),
+        Value::SignedInt(n) => Ok(BigInt::from(*n)),
+        Value::SignedBigInt(n) => Ok(BigInt::from(*n)),
+        Value::ArbitraryInt(n) => Ok(n.clone()),
+        Value::Rational(n) if n.is_integer() => Ok(n.to_integer()),
+        Value::Rational(_) => Err(Error::ImproperlyRational),
+        Value::Decimal(n) if n.fract().is_zero() => n
+            .to_i128()
+            .map(BigInt::from)
+            .ok_or(ArithmeticError::Overflow.into()),
+        Value::Decimal(_) => Err(Error::ImproperlyDecimal),
+        Value::Float(n) if n.fract() == 0.0 => {
+            num_traits::FromPrimitive::from_f64(*n).ok_or(ArithmeticError::Overflow.into())
+        }
+        Value::Float(_) => Err(Error::ImproperlyFloat),
+        Value::Complex(_) => Err(Error::ImproperlyComplex),
+    }
+}
+
+fn float(value: &Value) -> f64 {
+    *value.clone().promote_to_float()
+}
+
+/// A machine type together with its overflow behavior.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Machine {
+    pub ty: MachineType,
+    pub overflow: Overflow,
+}
+
+impl Machine {
+    /// Fit the exact result of integer arithmetic to the type.
+    fn fit(self, n: BigInt) -> Result {
+        let (min, max) = (self.ty.min(), self.ty.max());
+        let n = if n < min || n > max {
+            match self.overflow {
+                Overflow::Wrap => self.ty.wrap(&n),
+                Overflow::Saturate => n.clamp(min, max),
+                Overflow::Error => return Err(ArithmeticError::Overflow.into()),
+            }
+        } else {
+            n
+        };
+        Ok(Value::narrowest(n))
+    }
+
+    /// Convert a parsed literal to the type.
+    ///
+    /// Literals in bases other than 10 are bit patterns: on an `i8`, `0xff` is `-1`.
+    pub(crate) fn literal(self, value: Value, radix: u32, literal: &str) -> Result {
+        if self.ty.is_float() {
+            return Ok(self.ty.float(float(&value)));
+        }
+        let n = integer(&value)
+            .map_err(|_| Error::Unsupported(format!("non-integral literal {literal}")))?;
+        if radix != 10 && n < self.ty.modulus() {
+            return Ok(Value::narrowest(self.ty.wrap(&n)));
+        }
+        self.fit(n)
+    }
+
+    /// Convert a value of any kind to the type.
+    ///
+    /// Values which are not integers are an error for the integer types.
+    pub(crate) fn coerce(self, value: &Value) -> Result {
+        if self.ty.is_float() {
+            Ok(self.ty.float(float(value)))
+        } else {
+            self.fit(integer(value)?)
+        }
+    }
+
+    pub(crate) fn prefix(self, operator: PrefixOperator, operand: Value) -> Result {
+        if self.ty.is_float() {
+            return match operator {
+                PrefixOperator::Negation => Ok(self.ty.float(-float(&operand))),
+                PrefixOperator::Not => Err(Error::ImproperlyFloat),
+            };
+        }
+        let n = integer(&self.coerce(&operand)?)?;
+        match operator {
+            PrefixOperator::Negation => self.fit(-n),
+            PrefixOperator::Not => Ok(Value::narrowest(self.ty.wrap(&!n))),
+        }
+    }
+
+    pub(crate) fn infix(self, operator: InfixOperator, left: Value, right: Value) -> Result {
+        if self.ty.is_float() {
+            let (x, y) = (float(&left), float(&right));
+            // as for the integer types, a remainder by 0 is an error rather than NaN
+            if operator == InfixOperator::Rem && y == 0.0 {
+                return Err(ArithmeticError::DivideBy0.into());
+            }
+            let result = match operator {
+                InfixOperator::Add => x + y,
+                InfixOperator::Sub => x - y,
+                InfixOperator::Mul => x * y,
+                InfixOperator::Div => x / y,
+                InfixOperator::TruncDiv => (x / y).floor(),
+                InfixOperator::Rem => x % y,
+                InfixOperator::Pow => x.powf(y),
+                _ => return Err(Error::ImproperlyFloat),
+            };
+            return Ok(self.ty.float(result));
+        }
+
+        let a = integer(&self.coerce(&left)?)?;
+        // like Rust's `pow` and shifts, exponents and shift amounts need not be of the type
+        let b = match operator {
+            InfixOperator::Pow
+            | InfixOperator::Lshift
+            | InfixOperator::Rshift
+            | InfixOperator::RotateL
+            | InfixOperator::RotateR => integer(&right)?,
+            _ => integer(&self.coerce(&right)?)?,
+        };
+        let nonzero = |b: &BigInt| {
+            if b.is_zero() {
+                Err(Error::from(ArithmeticError::DivideBy0))
+            } else {
+                Ok(())
+            }
+        };
+        let bits = self.ty.bits();
+        // shift amounts may not be negative
+        let amount = || b.to_u32().ok_or(Error::from(ArithmeticError::Overflow));
+        let pattern = |n: BigInt| Ok(Value::narrowest(self.ty.wrap(&n)));
+        match operator {
+            InfixOperator::Add => self.fit(a + b),
+            InfixOperator::Sub => self.fit(a - b),
+            InfixOperator::Mul => self.fit(a * b),
+            InfixOperator::Div => {
+                nonzero(&b)?;
+                self.fit(a / b)
+            }
+            InfixOperator::TruncDiv => {
+                nonzero(&b)?;
+                let floor = if (&a % &b).is_zero() || a.is_negative() == b.is_negative() {
+                    &a / &b
+                } else {
+                    &a / &b - 1
+                };
+                self.fit(floor)
+            }
+            InfixOperator::Rem => {
+                nonzero(&b)?;
+                self.fit(a % b)
+            }
+            InfixOperator::Pow => self.pow(a, b),
+            InfixOperator::Lshift => match amount()? {
+                shift if shift >= bits => pattern(BigInt::zero()),
+                shift => pattern(self.ty.pattern(&a) << shift),
+            },
+            // `BigInt` shifts are arithmetic, so signed types keep their sign
+            InfixOperator::Rshift => pattern(a >> amount()?.min(bits)),
+            InfixOperator::RotateL | InfixOperator::RotateR => {
+                let shift = amount()? % bits;
+                let shift = match operator {
+                    InfixOperator::RotateL => shift,
+                    _ => (bits - shift) % bits,
+                };
+                let n = self.ty.pattern(&a);
+                pattern((&n << shift) | (n >> (bits - shift)))
+            }
+            InfixOperator::BitAnd => pattern(a & b),
+            InfixOperator::BitOr => pattern(a | b),
+            InfixOperator::BitXor => pattern(a ^ b),
+            _ => unreachable!("comparisons and logical operators are not arithmetic"),
+        }
+    }
+
+    /// Raise `base` to the power of `exponent`, as the type.
+    fn pow(self, base: BigInt, exponent: BigInt) -> Result {
+        if exponent.is_negative() {
+            // the reciprocal of an integer, truncated towards zero
+            return match base.to_i8() {
+                Some(0) => Err(ArithmeticError::DivideBy0.into()),
+                Some(1) => Ok(Value::narrowest(base)),
+                Some(-1) if (&exponent % 2u8).is_zero() => Ok(Value::narrowest(BigInt::one())),
+                Some(-1) => Ok(Value::narrowest(base)),
+                _ => Ok(Value::narrowest(BigInt::zero())),
+            };
+        }
+        if self.overflow == Overflow::Wrap {
+            let modulus = self.ty.modulus();
+            let power = self.ty.pattern(&base).modpow(&exponent, &modulus);
+            return Ok(Value::narrowest(self.ty.wrap(&power)));
+        }
+        // any other base overflows 128 bits within 128 multiplications
+        match exponent.to_u32() {
+            Some(exponent) if base.abs() <= BigInt::one() || exponent <= 128 => {
+                self.fit(base.pow(exponent))
+            }
+            _ => {
+                let negative = base.is_negative() && (&exponent % 2u8).is_one();
+                self.fit(if negative {
+                    self.ty.min() - 1
+                } else {
+                    self.ty.max() + 1
+                })
+            }
+        }
+    }
+
+    pub(crate) fn function(self, function: Function, args: Vec<Value>) -> Result {
+        let args = args
+            .iter()
+            .map(|arg| self.coerce(arg))
+            .collect::<Result<Vec<_>>>()?;
+        if self.ty.is_float() {
+            let result =
+                <Value as crate::types::Calcable>::function(function, args, crate::Backend::Float)?;
+            return Ok(self.ty.float(float(&result)));
+        }
+        if function.is_bitwise() {
+            let result = integer(&Value::bit_function(function, args, Some(self.ty.bits()))?)?;
+            return match function {
+                // like the bitwise operators, these rearrange the bit pattern and never overflow
+                Function::Bswap | Function::Bitrev | Function::Getbits | Function::Setbits => {
+                    Ok(Value::narrowest(self.ty.wrap(&result)))
+                }
+                _ => self.fit(result),
+            };
+        }
+        let mut args = args.iter().map(integer).collect::<Result<Vec<_>>>()?;
+        match function {
+            Function::Abs => self.fit(args.remove(0).abs()),
+            Function::Ceil | Function::Floor | Function::Round => self.fit(args.remove(0)),
+            Function::Min => self.fit(args.into_iter().min().expect("arity has been checked")),
+            Function::Max => self.fit(args.into_iter().max().expect("arity has been checked")),
+            Function::Clamp => {
+                let [x, lo, hi]: [BigInt; 3] = args.try_into().expect("arity has been checked");
+                // unlike `Ord::clamp`, an inverted range does not panic: `hi` wins
+                self.fit(x.max(lo).min(hi))
+            }
+            Function::Factorial => {
+                let n = args.remove(0);
+                if n.is_negative() {
+                    return Err(Error::Domain(<&str>::from(function).to_owned()));
+                }
+                // as a chain of `*`, each of which overflows; this reaches 0 or the maximum quickly
+                let mut product = BigInt::one();
+                let mut k = BigInt::from(2);
+                while k <= n && !product.is_zero() && product != self.ty.max() {
+                    product = integer(&self.fit(product * &k)?)?;
+                    k += 1;
+                }
+                Ok(Value::narrowest(product))
+            }
+            _ => Err(Error::Unsupported(format!(
+                "{} on integers",
+                <&str>::from(function)
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn machine(ty: MachineType, overflow: Overflow) -> Machine {
+        Machine { ty, overflow }
+    }
+
+    #[rstest]
+    #[case(MachineType::U8, Overflow::Wrap, 300, 44)]
+    #[case(MachineType::U8, Overflow::Saturate, 300, 255)]
+    #[case(MachineType::U8, Overflow::Saturate, -5, 0)]
+    #[case(MachineType::I8, Overflow::Wrap, 128, -128)]
+    #[case(MachineType::I8, Overflow::Saturate, -200, -128)]
+    #[case(MachineType::I16, Overflow::Error, 1000, 1000)]
+    fn fit(
+        #[case] ty: MachineType,
+        #[case] overflow: Overflow,
+        #[case] n: i32,
+        #[case] expect: i32,
+    ) {
+        let fitted = machine(ty, overflow).fit(BigInt::from(n)).unwrap();
+        assert_eq!(fitted, Value::from(expect));
+    }
+
+    #[test]
+    fn fit_error() {
+        assert!(matches!(
+            machine(MachineType::U8, Overflow::Error).fit(BigInt::from(256)),
+            Err(Error::Arithmetic(ArithmeticError::Overflow))
+        ));
+    }
+
+    #[rstest]
+    #[case(MachineType::U8, 0xf0, 0xf0)]
+    #[case(MachineType::I8, 0xff, -1)]
+    #[case(MachineType::I8, 0x7f, 127)]
+    #[case(MachineType::U128, 1, 1)]
+    fn bit_pattern_literal(#[case] ty: MachineType, #[case] n: u32, #[case] expect: i32) {
+        let value = machine(ty, Overflow::Error)
+            .literal(Value::from(n), 16, "")
+            .unwrap();
+        assert_eq!(value, Value::from(expect));
+    }
+
+    #[rstest]
+    #[case(MachineType::U8, InfixOperator::RotateL, 0x81, 1, 0x03)]
+    #[case(MachineType::U8, InfixOperator::RotateL, 0xff, 8, 0xff)]
+    #[case(MachineType::U8, InfixOperator::RotateR, 0x01, 1, 0x80)]
+    #[case(MachineType::I8, InfixOperator::RotateL, -128, 1, 1)]
+    #[case(MachineType::U8, InfixOperator::Lshift, 0x81, 1, 0x02)]
+    #[case(MachineType::U8, InfixOperator::Lshift, 1, 8, 0)]
+    #[case(MachineType::I8, InfixOperator::Rshift, -128, 7, -1)]
+    #[case(MachineType::I8, InfixOperator::Rshift, -128, 100, -1)]
+    fn shifts(
+        #[case] ty: MachineType,
+        #[case] operator: InfixOperator,
+        #[case] n: i32,
+        #[case] amount: u32,
+        #[case] expect: i32,
+    ) {
+        let value = machine(ty, Overflow::Error)
+            .infix(operator, n.into(), amount.into())
+            .unwrap();
+        assert_eq!(value, Value::from(expect));
+    }
+
+    #[rstest]
+    #[case(MachineType::U8, 0x0f, 0xf0)]
+    #[case(MachineType::U16, 0x0f, 0xfff0)]
+    #[case(MachineType::I8, 0, -1)]
+    fn not(#[case] ty: MachineType, #[case] n: i32, #[case] expect: i32) {
+        let value = machine(ty, Overflow::Error)
+            .prefix(PrefixOperator::Not, n.into())
+            .unwrap();
+        assert_eq!(value, Value::from(expect));
+    }
+
+    #[rstest]
+    #[case(MachineType::U8, Function::Clz, vec![1], 7)]
+    #[case(MachineType::U16, Function::Bswap, vec![0x1234], 0x3412)]
+    #[case(MachineType::I8, Function::Bitrev, vec![1], -128)]
+    #[case(MachineType::I8, Function::Popcount, vec![-1], 8)]
+    #[case(MachineType::U32, Function::Ctz, vec![0, 8], 8)]
+    #[case(MachineType::U8, Function::NextPow2, vec![100], 128)]
+    #[case(MachineType::I8, Function::Getbits, vec![-1, 7, 0], -1)]
+    #[case(MachineType::I8, Function::Setbits, vec![0, 7, 7, 1], -128)]
+    fn bit_functions(
+        #[case] ty: MachineType,
+        #[case] function: Function,
+        #[case] args: Vec<i32>,
+        #[case] expect: i32,
+    ) {
+        let args = args.into_iter().map(Value::from).collect();
+        let value = machine(ty, Overflow::Error)
+            .function(function, args)
+            .unwrap();
+        assert_eq!(value, Value::from(expect));
+    }
+
+    #[rstest]
+    #[case(MachineType::U8, Overflow::Wrap, 5, 120)]
+    #[case(MachineType::U8, Overflow::Wrap, 6, 208)]
+    #[case(MachineType::U8, Overflow::Saturate, 6, 255)]
+    #[case(MachineType::I8, Overflow::Saturate, 100, 127)]
+    #[case(MachineType::U64, Overflow::Wrap, 100, 0)]
+    #[case(MachineType::I128, Overflow::Wrap, 0, 1)]
+    fn factorial(
+        #[case] ty: MachineType,
+        #[case] overflow: Overflow,
+        #[case] n: i32,
+        #[case] expect: i32,
+    ) {
+        let value = machine(ty, overflow)
+            .function(Function::Factorial, vec![n.into()])
+            .unwrap();
+        assert_eq!(value, Value::from(expect));
+        assert!(machine(ty, Overflow::Error)
+            .function(Function::Factorial, vec![Value::from(-1)])
+            .is_err());
+    }
+
+    #[rstest]
+    #[case(Overflow::Wrap, 3, 6, 217)]
+    #[case(Overflow::Saturate, 3, 6, 255)]
+    #[case(Overflow::Saturate, 2, 1000, 255)]
+    #[case(Overflow::Error, 2, 7, 128)]
+    #[case(Overflow::Error, 1, 1000, 1)]
+    #[case(Overflow::Wrap, 2, -1, 0)]
+    fn pow(
+        #[case] overflow: Overflow,
+        #[case] base: i32,
+        #[case] exponent: i32,
+        #[case] expect: i32,
+    ) {
+        let value = machine(MachineType::U8, overflow)
+            .infix(InfixOperator::Pow, base.into(), exponent.into())
+            .unwrap();
+        assert_eq!(value, Value::from(expect));
+    }
+
+    #[rstest]
+    #[case(InfixOperator::Div, -7, 2, -3)]
+    #[case(InfixOperator::TruncDiv, -7, 2, -4)]
+    #[case(InfixOperator::Rem, -7, 2, -1)]
+    fn division(
+        #[case] operator: InfixOperator,
+        #[case] left: i32,
+        #[case] right: i32,
+        #[case] expect: i32,
+    ) {
+        let value = machine(MachineType::I32, Overflow::Error)
+            .infix(operator, left.into(), right.into())
+            .unwrap();
+        assert_eq!(value, Value::from(expect));
+    }
+
+    #[rstest]
+    #[case::truncation(MachineType::U8, Value::from(0x1ff), Value::UnsignedInt(0xff))]
+    #[case::sign_extension(MachineType::U16, Value::from(-1), Value::UnsignedInt(0xffff))]
+    #[case::reinterpretation(MachineType::I8, Value::from(0xff), Value::SignedInt(-1))]
+    #[case::widening(MachineType::I128, Value::from(-1), Value::SignedBigInt(-1))]
+    #[case::signed_order(MachineType::I64, Value::from(5u8), Value::SignedInt(5))]
+    #[case::unsigned_order(MachineType::U128, Value::from(5), Value::UnsignedBigInt(5))]
+    #[case::float_saturates(MachineType::U8, Value::Float(300.7), Value::UnsignedInt(255))]
+    #[case::float_truncates(MachineType::I8, Value::Float(-2.5), Value::SignedInt(-2))]
+    #[case::infinity(MachineType::I16, Value::Float(f64::NEG_INFINITY), Value::SignedInt(-32768))]
+    #[case::nan(MachineType::U32, Value::Float(f64::NAN), Value::UnsignedInt(0))]
+    #[case::fraction(MachineType::U8, Value::from(7) / Value::from(2), Value::UnsignedInt(3))]
+    #[case::to_float(MachineType::F32, Value::Float(0.1), Value::Float(0.1_f32 as f64))]
+    fn cast(#[case] ty: MachineType, #[case] value: Value, #[case] expect: Value) {
+        let cast = ty.cast(&value).unwrap();
+        assert_eq!(cast, expect);
+        assert_eq!(
+            std::mem::discriminant(&cast),
+            std::mem::discriminant(&expect)
+        );
+    }
+
+    #[test]
+    fn float_precision() {
+        let value = machine(MachineType::F32, Overflow::Wrap)
+            .infix(InfixOperator::Add, 0.1.into(), 0.2.into())
+            .unwrap();
+        assert_eq!(value, Value::Float(0.3_f32 as f64));
+    }
+}
@@ -1,17 +1,30 @@
 mod arithmetic;
 mod bitwise;
+mod calcable;
 mod comparison;
 mod conversion;
+pub(crate) mod datetime;
 mod error;
 mod format;
+mod machine;
 mod numeric;
 mod parsing;
+mod quantity;
+mod units;
 
 use std::{cmp::Ordering, f64};
 
-use num_traits::ToPrimitive as _;
+use num_bigint::BigInt;
+use num_complex::Complex64;
+use num_rational::BigRational;
+use num_traits::{FromPrimitive as _, One as _, Signed as _, ToPrimitive as _, Zero as _};
+use rust_decimal::Decimal;
 
 pub use error::{ArithmeticError, Error, ParseValueError};
+pub(crate) use machine::Machine;
+pub use machine::{MachineType, Overflow};
+pub use quantity::Quantity;
+pub use units::{Dimension, ParseDimensionError};
 
 /// Dispatch an operation across the variants of a value.
 ///
@@ -56,11 +69,31 @@ macro_rules! dispatch_operation {
                 let $n = n;
                 $op(rhs)
             }
+            Value::ArbitraryInt(n) => {
+                let rhs = num_bigint::BigInt::try_from($rhs).expect("orders must match");
+                let $n = n;
+                $op(rhs)
+            }
+            Value::Rational(n) => {
+                let rhs = num_rational::BigRational::try_from($rhs).expect("orders must match");
+                let $n = n;
+                $op(rhs)
+            }
+            Value::Decimal(n) => {
+                let rhs = rust_decimal::Decimal::try_from($rhs).expect("orders must match");
+                let $n = n;
+                $op(rhs)
+            }
             Value::Float(n) => {
                 let rhs = f64::try_from($rhs).expect("orders must match");
                 let $n = n;
                 $op(rhs)
             }
+            Value::Complex(n) => {
+                let rhs = num_complex::Complex64::try_from($rhs).expect("orders must match");
+                let $n = n;
+                $op(rhs)
+            }
         }
     }};
     (INTS: $lhs:expr, $rhs:expr, $n:ident, $op:expr) => {{
@@ -92,7 +125,15 @@ macro_rules! dispatch_operation {
                 let $n = n;
                 Ok($op(rhs))
             }
+            Value::ArbitraryInt(n) => {
+                let rhs = num_bigint::BigInt::try_from($rhs).expect("orders must match");
+                let $n = n;
+                Ok($op(rhs))
+            }
+            Value::Rational(_) => Err(Error::ImproperlyRational),
+            Value::Decimal(_) => Err(Error::ImproperlyDecimal),
             Value::Float(_) => Err(Error::ImproperlyFloat),
+            Value::Complex(_) => Err(Error::ImproperlyComplex),
         }
     }};
 }
@@ -113,7 +154,11 @@ pub(crate) use dispatch_operation;
 /// 1. `u128`
 /// 1. `i64`
 /// 1. `i128`
+/// 1. [`BigInt`] (arbitrary precision)
+/// 1. [`BigRational`] (exact fractions)
+/// 1. [`Decimal`] (base-10 fixed point)
 /// 1. `f64`
+/// 1. [`Complex64`] (complex floats)
 ///
 /// Note that in general, lower orders have a narrower scope and higher orders
 /// have a broader scope. This enables us to promote values to higher compatible orders
@@ -130,12 +175,20 @@ pub(crate) use dispatch_operation;
 ///
 ///   I.e. the value `u64::MAX` would be promoted to `i128`, skipping `i64`, as it could
 ///   not be losslessly converted.
-///   The value `i128::MAX + 1` would be promoted to `f64`, _even though this will lose
-///   precision_, because `f64` can better approximate that overflow than `i128` could.
+///   The value `i128::MAX + 1` would be promoted to [`BigInt`].
 /// - `i64` values are unconditionally promoted to `i128` as that conversion is infallible.
-/// - `i128` values are unconditionally promoted to `f64` as that type can better approximate
-///   very large values.
-/// - `f64` values remain `f64`.
+/// - `i128` values are unconditionally promoted to [`BigInt`] as that conversion is infallible.
+/// - [`BigInt`] values are unconditionally promoted to [`BigRational`] as that conversion is infallible.
+/// - [`BigRational`] values are promoted to [`Decimal`] if they fit within its range, and to
+///   `f64` otherwise. Either may lose precision, but this only happens when combined with a
+///   decimal or float, or when a result would need more than [`MAX_ARBITRARY_BITS`] bits.
+/// - [`Decimal`] values are promoted to `f64`, _even though this will lose precision_.
+/// - `f64` values are promoted to [`Complex64`] with an imaginary part of 0.
+/// - [`Complex64`] values remain [`Complex64`].
+///
+/// Fractions are always stored in lowest terms, and a fraction whose denominator is 1
+/// is demoted to the narrowest integer order when it is constructed. Likewise, a complex
+/// number whose imaginary part is 0 is demoted to `f64`.
 ///
 /// ## Parsing Rules
 ///
@@ -150,6 +203,13 @@ pub(crate) use dispatch_operation;
 /// - if the two orders are still not equal, the previous step is repeated
 /// - once the two orders are equal, math is performed as normal.
 ///
+/// Division is the exception: integers and fractions are promoted to at least [`BigRational`]
+/// first, so that the quotient is exact. Division or remainder by an exact zero is an error,
+/// while a float zero divisor gives the IEEE 754 result.
+///
+/// Integer operations which would overflow their order promote and retry,
+/// so integer arithmetic stays exact until it would exceed [`MAX_ARBITRARY_BITS`].
+///
 /// ## Equality and Comparison
 ///
 /// Equality and comparison operations are defined on the logical values.
@@ -161,7 +221,6 @@ pub(crate) use dispatch_operation;
 #[derive(
     Debug,
     Clone,
-    Copy,
     strum::EnumDiscriminants,
     derive_more::From,
     derive_more::TryInto,
@@ -180,22 +239,54 @@ pub enum Value {
     UnsignedBigInt(u128),
     SignedInt(i64),
     SignedBigInt(i128),
+    #[lower_exp("{_0}")]
+    #[upper_exp("{_0}")]
+    ArbitraryInt(BigInt),
+    #[from(skip)]
+    #[lower_exp("{_0}")]
+    #[upper_exp("{_0}")]
+    Rational(BigRational),
+    #[binary("{_0}")]
+    #[octal("{_0}")]
+    #[lower_hex("{_0}")]
+    #[upper_hex("{_0}")]
+    Decimal(Decimal),
     #[binary("{_0}")]
     #[octal("{_0}")]
     #[lower_hex("{_0}")]
     #[upper_hex("{_0}")]
     Float(f64),
+    #[from(skip)]
+    #[display("{}", format::Rectangular(_0))]
+    #[binary("{}", format::Rectangular(_0))]
+    #[octal("{}", format::Rectangular(_0))]
+    #[lower_hex("{}", format::Rectangular(_0))]
+    #[upper_hex("{}", format::Rectangular(_0))]
+    Complex(Complex64),
 }
 
+/// The largest magnitude, in bits, of an arbitrary-precision integer.
+///
+/// Integer results which would be larger than this are promoted to floats instead,
+/// so that expressions like `10**10**10` cannot exhaust memory.
+pub const MAX_ARBITRARY_BITS: u64 = 1 << 18;
+
 pub(crate) type Result<T = Value, E = Error> = std::result::Result<T, E>;
 
 impl Value {
     pub const PI: Self = Self::Float(f64::consts::PI);
     pub const E: Self = Self::Float(f64::consts::E);
+    pub const I: Self = Self::Complex(Complex64::I);
+    pub const TAU: Self = Self::Float(f64::consts::TAU);
+    pub const PHI: Self = Self::Float(1.618_033_988_749_895);
+    pub const INFINITY: Self = Self::Float(f64::INFINITY);
+    pub const NAN: Self = Self::Float(f64::NAN);
+    pub const SQRT_2: Self = Self::Float(f64::consts::SQRT_2);
+    pub const LN_2: Self = Self::Float(f64::consts::LN_2);
 
     /// Get the order of this value
     pub(crate) fn order(&self) -> Order {
-        Order::from(*self)
+        Order::from(self)
     }
 
     /// Promote this value according to its value.
@@ -206,29 +297,38 @@ impl Value {
     ///
     ///   I.e. the value `u64::MAX` would be promoted to `i128`, skipping `i64`, as it could
     ///   not be losslessly converted.
-    ///   The value `i128::MAX + 1` would be promoted to `f64`, _even though this will lose
-    ///   precision_, because `f64` can better approximate that overflow than `i128` could.
+    ///   The value `i128::MAX + 1` would be promoted to [`BigInt`].
     /// - `i64` values are unconditionally promoted to `i128` as that conversion is infallible.
-    /// - `i128` values are unconditionally promoted to `f64` as that type can better approximate
-    ///   very large values.
-    /// - `f64` values remain `f64`.
+    /// - `i128` values are unconditionally promoted to [`BigInt`] as that conversion is infallible.
+    /// - [`BigInt`] values are unconditionally promoted to [`BigRational`] as that conversion is infallible.
+    /// - [`BigRational`] values are promoted to [`Decimal`] if they fit within its range, and to
+    ///   `f64` otherwise.
+    /// - [`Decimal`] values are promoted to `f64`, _even though this will lose precision_.
+    /// - `f64` values are promoted to [`Complex64`] with an imaginary part of 0.
+    /// - [`Complex64`] values remain [`Complex64`].
     pub(crate) fn promote(&mut self) {
-        *self = match *self {
-            Value::UnsignedInt(n) => Self::UnsignedBigInt(n as _),
+        *self = match self {
+            Value::UnsignedInt(n) => Self::UnsignedBigInt(*n as _),
             Value::UnsignedBigInt(n) => {
                 const SI_MAX: u128 = i64::MAX as _;
                 const SBI_MIN: u128 = SI_MAX + 1;
                 const SBI_MAX: u128 = i128::MAX as _;
 
-                match n {
-                    0..=SI_MAX => Self::SignedInt(n as _),
-                    SBI_MIN..=SBI_MAX => Self::SignedBigInt(n as _),
-                    _ => Self::Float(n.to_f64().expect("all u128 convert to f64")),
+                match *n {
+                    0..=SI_MAX => Self::SignedInt(*n as _),
+                    SBI_MIN..=SBI_MAX => Self::SignedBigInt(*n as _),
+                    _ => Self::ArbitraryInt((*n).into()),
                 }
             }
-            Value::SignedInt(n) => Self::SignedBigInt(n as _),
-            Value::SignedBigInt(n) => Self::Float(n.to_f64().expect("all i128 convert to f64")),
-            Value::Float(n) => Self::Float(n),
+            Value::SignedInt(n) => Self::SignedBigInt(*n as _),
+            Value::SignedBigInt(n) => Self::ArbitraryInt((*n).into()),
+            // not `From`, which would demote straight back to an integer
+            Value::ArbitraryInt(n) => Self::Rational(BigRational::from_integer(std::mem::take(n))),
+            Value::Rational(n) => rational_to_decimal(n)
+                .map_or_else(|| Self::Float(rational_to_f64(n)), Self::Decimal),
+            Value::Decimal(n) => Self::Float(n.to_f64().expect("all decimals convert to f64")),
+            Value::Float(n) => Self::Complex(Complex64::from(*n)),
+            Value::Complex(_) => return,
         }
     }
 
@@ -239,15 +339,30 @@ impl Value {
         }
     }
 
-    /// Promote this value until it is a float.
+    /// Promote this value until it is at least a fraction.
+    pub(crate) fn promote_to_rational(&mut self) {
+        while self.order() < Order::Rational {
+            self.promote();
+        }
+    }
+
+    /// Convert this value to a float.
+    ///
+    /// Real values are promoted. Complex values keep their real part if their imaginary part is 0,
+    /// and become NaN otherwise; use [`Value::try_promote_to_float`] where they may occur.
     pub(crate) fn promote_to_float(&mut self) -> &mut f64 {
         // there is no case where an integer value produces NaN when converted to a float
-        *self = match *self {
-            Value::UnsignedInt(n) => (n as f64).into(),
-            Value::UnsignedBigInt(n) => (n as f64).into(),
-            Value::SignedInt(n) => (n as f64).into(),
-            Value::SignedBigInt(n) => (n as f64).into(),
-            Value::Float(n) => n.into(),
+        *self = match self {
+            Value::UnsignedInt(n) => (*n as f64).into(),
+            Value::UnsignedBigInt(n) => (*n as f64).into(),
+            Value::SignedInt(n) => (*n as f64).into(),
+            Value::SignedBigInt(n) => (*n as f64).into(),
+            Value::ArbitraryInt(n) => bigint_to_f64(n).into(),
+            Value::Rational(n) => rational_to_f64(n).into(),
+            Value::Decimal(n) => n.to_f64().expect("all decimals convert to f64").into(),
+            Value::Float(n) => (*n).into(),
+            Value::Complex(n) if n.im == 0.0 => n.re.into(),
+            Value::Complex(_) => f64::NAN.into(),
         };
         let Self::Float(ref mut f) = self else {
             unreachable!("we just promoted up to float")
@@ -255,52 +370,135 @@ impl Value {
         f
     }
 
-    /// Demote this value to the narrowest valid container type
-    pub(crate) fn demote(&mut self) {
-        const ZERO: f64 = 0.0;
-        const UI_MAX: f64 = u64::MAX as _;
-        const UBI_MAX: f64 = u128::MAX as _;
-        const SI_MIN: f64 = i64::MIN as _;
-        const SI_MAX: f64 = i64::MAX as _;
-        const SBI_MIN: f64 = i128::MIN as _;
-        const SBI_MAX: f64 = i128::MAX as _;
+    /// Convert this value to a float, unless it is complex with a nonzero imaginary part.
+    pub(crate) fn try_promote_to_float(&mut self) -> Result<&mut f64> {
+        match self {
+            Value::Complex(n) if n.im != 0.0 => Err(Error::ImproperlyComplex),
+            _ => Ok(self.promote_to_float()),
+        }
+    }
 
-        let value = *self.clone().promote_to_float();
-        debug_assert!(
-            value.fract().abs() < f64::EPSILON,
-            "we should never demote values not already known to be integral"
-        );
+    /// Express a fraction as a decimal: exactly, if its expansion terminates and fits a [`Decimal`],
+    /// and as the nearest float otherwise. Other values are unchanged.
+    ///
+    /// So `3/8` is `0.375`, but `1/3` is `0.3333333333333333`.
+    pub(crate) fn fraction_to_decimal(self) -> Value {
+        let Value::Rational(n) = &self else {
+            return self;
+        };
+        // an expansion terminates if the denominator has no prime factors but 2 and 5
+        let mut rest = n.denom().magnitude() >> n.denom().trailing_zeros().unwrap_or(0);
+        while (&rest % 5_u8).is_zero() {
+            rest /= 5_u8;
+        }
+        match rational_to_decimal(n) {
+            Some(decimal) if rest.is_one() => decimal.into(),
+            _ => rational_to_f64(n).into(),
+        }
+    }
+
+    /// Whether converting this value to a float keeps it exact.
+    ///
+    /// Floats and complex values are trivially exact; so is `2**53`, but `2**53 + 1` and `1/3` are not.
+    pub(crate) fn fits_float(&self) -> bool {
+        let exact = match self {
+            Value::Float(_) | Value::Complex(_) => return true,
+            Value::Decimal(n) => BigRational::new(
+                BigInt::from(n.mantissa()),
+                num_traits::Pow::pow(BigInt::from(10), n.scale()),
+            ),
+            Value::Rational(n) => n.clone(),
+            n => {
+                let mut n = n.clone();
+                n.promote_to_rational();
+                let Value::Rational(n) = n else {
+                    unreachable!("we just promoted up to rational")
+                };
+                n
+            }
+        };
+        let float = *self.clone().promote_to_float();
+        BigRational::from_float(float).is_some_and(|float| float == exact)
+    }
 
-        let narrowest_order = [
-            (ZERO..=UI_MAX, Order::UnsignedInt),
-            (ZERO..=UBI_MAX, Order::UnsignedBigInt),
-            (SI_MIN..=SI_MAX, Order::SignedInt),
-            (SBI_MIN..=SBI_MAX, Order::SignedBigInt),
-        ]
-        .into_iter()
-        .find_map(|(range, order)| range.contains(&value).then_some(order))
-        .unwrap_or(Order::Float);
+    /// Whether an operation which produced `result` from `operands` rounded an exact operand,
+    /// such as an integer above `2**53`, to a float.
+    pub(crate) fn lost_precision<'a>(
+        operands: impl IntoIterator<Item = &'a Value>,
+        result: &Value,
+    ) -> bool {
+        result.order() >= Order::Float && !operands.into_iter().all(Value::fits_float)
+    }
+
+    /// Promote this value until it is complex.
+    pub(crate) fn promote_to_complex(&mut self) -> &mut Complex64 {
+        if self.order() != Order::Complex {
+            *self = Self::Complex(Complex64::from(*self.promote_to_float()));
+        }
+        let Self::Complex(ref mut z) = self else {
+            unreachable!("we just promoted up to complex")
+        };
+        z
+    }
 
-        // rhs isn't really necessary, except structurally, for the `dispatch_operation` macro
-        // maybe it would just vanish under optimization?
-        let mut rhs = *self;
+    /// Demote this value to the narrowest valid container type
+    ///
+    /// Floats are only demoted into the fixed-width integer orders; larger floats remain floats.
+    /// Complex values are never demoted.
+    pub(crate) fn demote(&mut self) {
+        const SBI_MIN: f64 = i128::MIN as _;
+        const UBI_MAX: f64 = u128::MAX as _;
 
-        *self = dispatch_operation!(self, rhs, n, |_rhs| {
-            // due to the nature of the macro we're 100% going to perform at least one unnecessary
-            // cast in every expansion branch of this macro; can't be helped
-            #[expect(clippy::unnecessary_cast)]
-            match narrowest_order {
-                Order::UnsignedInt => (*n as u64).into(),
-                Order::UnsignedBigInt => (*n as u128).into(),
-                Order::SignedInt => (*n as i64).into(),
-                Order::SignedBigInt => (*n as i128).into(),
-                Order::Float => (*n as f64).into(),
+        let n = match self {
+            Value::UnsignedInt(n) => BigInt::from(*n),
+            Value::UnsignedBigInt(n) => BigInt::from(*n),
+            Value::SignedInt(n) => BigInt::from(*n),
+            Value::SignedBigInt(n) => BigInt::from(*n),
+            Value::ArbitraryInt(n) => std::mem::take(n),
+            Value::Rational(n) if n.is_integer() => n.to_integer(),
+            Value::Rational(_) => return,
+            Value::Decimal(n) if n.fract().is_zero() => {
+                BigInt::from(n.to_i128().expect("decimals are narrower than i128"))
+            }
+            Value::Decimal(_) => return,
+            Value::Float(n) => {
+                if !(SBI_MIN..=UBI_MAX).contains(n) {
+                    return;
+                }
+                debug_assert!(
+                    n.fract().abs() < f64::EPSILON,
+                    "we should never demote values not already known to be integral"
+                );
+                BigInt::from_f64(*n).expect("finite floats convert to BigInt")
             }
-        });
+            Value::Complex(_) => return,
+        };
+        *self = Self::narrowest(n);
+    }
+
+    /// Represent an integer in the narrowest order which can hold it.
+    pub(crate) fn narrowest(n: BigInt) -> Self {
+        if let Some(n) = n.to_u64() {
+            Self::UnsignedInt(n)
+        } else if let Some(n) = n.to_u128() {
+            Self::UnsignedBigInt(n)
+        } else if let Some(n) = n.to_i64() {
+            Self::SignedInt(n)
+        } else if let Some(n) = n.to_i128() {
+            Self::SignedBigInt(n)
+        } else {
+            Self::ArbitraryInt(n)
+        }
     }
 
     /// Find the minimum compatible order for `self` and `other` by promoting the lesser until they match.
     pub(crate) fn match_orders(&mut self, other: &mut Self) {
+        // going through the intermediate orders would round a fraction to a decimal first
+        if self.order() == Order::Float && other.order() < Order::Float {
+            other.promote_to_float();
+        } else if other.order() == Order::Float && self.order() < Order::Float {
+            self.promote_to_float();
+        }
         while self.order() != other.order() {
             match self.order().cmp(&other.order()) {
                 Ordering::Equal => unreachable!("orders already known not to be equal"),
@@ -310,3 +508,45 @@ impl Value {
         }
     }
 }
+
+impl From<BigRational> for Value {
+    fn from(n: BigRational) -> Self {
+        if n.is_integer() {
+            Self::narrowest(n.to_integer())
+        } else {
+            Self::Rational(n)
+        }
+    }
+}
+
+impl From<Complex64> for Value {
+    fn from(n: Complex64) -> Self {
+        if n.im == 0.0 {
+            Self::Float(n.re)
+        } else {
+            Self::Complex(n)
+        }
+    }
+}
+
+/// Convert a fraction to the nearest decimal, if it is within the range of [`Decimal`].
+pub(crate) fn rational_to_decimal(n: &BigRational) -> Option<Decimal> {
+    let numer = Decimal::from_i128(n.numer().to_i128()?)?;
+    let denom = Decimal::from_i128(n.denom().to_i128()?)?;
+    numer.checked_div(denom)
+}
+
+/// Convert a fraction to the nearest float, saturating to infinity.
+fn rational_to_f64(n: &BigRational) -> f64 {
+    n.to_f64()
+        .unwrap_or_else(|| bigint_to_f64(n.numer()) / bigint_to_f64(n.denom()))
+}
+
+/// Convert an arbitrary-precision integer to the nearest float, saturating to infinity.
+fn bigint_to_f64(n: &BigInt) -> f64 {
+    n.to_f64().unwrap_or(if n.is_negative() {
+        f64::NEG_INFINITY
+    } else {
+        f64::INFINITY
+    })
+}
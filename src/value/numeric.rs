@@ -1,299 +1,465 @@
 use crate::Value;
 
-use super::{ArithmeticError, Error, Result};
+use num_bigint::BigInt;
+use num_complex::Complex64;
+use num_traits::{One as _, Pow, Signed as _, ToPrimitive as _};
+use rust_decimal::{MathematicalOps as _, RoundingStrategy};
+
+use super::{ArithmeticError, Error, Order, Result, MAX_ARBITRARY_BITS};
 
 impl Value {
-    pub(crate) fn as_u32(self) -> Result<u32> {
+    pub(crate) fn as_u32(&self) -> Result<u32> {
         match self {
-            Value::UnsignedInt(n) => u32::try_from(n).map_err(|_| ArithmeticError::Overflow.into()),
+            Value::UnsignedInt(n) => {
+                u32::try_from(*n).map_err(|_| ArithmeticError::Overflow.into())
+            }
             Value::UnsignedBigInt(n) => {
-                u32::try_from(n).map_err(|_| ArithmeticError::Overflow.into())
+                u32::try_from(*n).map_err(|_| ArithmeticError::Overflow.into())
             }
-            Value::SignedInt(n) => u32::try_from(n).map_err(|_| ArithmeticError::Overflow.into()),
+            Value::SignedInt(n) => u32::try_from(*n).map_err(|_| ArithmeticError::Overflow.into()),
             Value::SignedBigInt(n) => {
+                u32::try_from(*n).map_err(|_| ArithmeticError::Overflow.into())
+            }
+            Value::ArbitraryInt(n) => {
                 u32::try_from(n).map_err(|_| ArithmeticError::Overflow.into())
             }
+            Value::Rational(_) => Err(Error::ImproperlyRational),
+            Value::Decimal(n) => {
+                if !n.fract().is_zero() {
+                    return Err(Error::ImproperlyDecimal);
+                }
+                n.to_u32().ok_or_else(|| ArithmeticError::Overflow.into())
+            }
+            Value::Complex(_) => Err(Error::ImproperlyComplex),
             Value::Float(n) => {
-                if n < 0.0 {
+                if *n < 0.0 {
                     return Err(ArithmeticError::Overflow.into());
                 }
                 if n.fract() != 0.0 {
                     return Err(Error::ImproperlyFloat);
                 }
                 // a 64-bit integer has at least enough precision to capture the integer part of this number
-                let n = n as u64;
+                let n = *n as u64;
 
                 u32::try_from(n).map_err(|_| ArithmeticError::Overflow.into())
             }
         }
     }
 
+    /// This value as a whole number, if it is one which fits in an `i128`.
+    pub(crate) fn to_i128(&self) -> Option<i128> {
+        match self {
+            Value::UnsignedInt(n) => Some(i128::from(*n)),
+            Value::UnsignedBigInt(n) => i128::try_from(*n).ok(),
+            Value::SignedInt(n) => Some(i128::from(*n)),
+            Value::SignedBigInt(n) => Some(*n),
+            Value::ArbitraryInt(n) => n.to_i128(),
+            Value::Rational(n) => n.is_integer().then(|| n.to_integer().to_i128())?,
+            Value::Decimal(n) => n.fract().is_zero().then(|| n.to_i128())?,
+            Value::Float(n) => (n.fract() == 0.0).then(|| n.to_i128())?,
+            Value::Complex(_) => None,
+        }
+    }
+
     /// Divide this value by another, flooring the result to the next lowest integer.
     pub fn trunc_div(mut self, other: impl Into<Self>) -> Self {
         self /= other;
-        if let Value::Float(n) = &mut self {
-            *n = n.floor();
+        match &mut self {
+            Value::Float(n) => *n = n.floor(),
+            Value::Rational(n) => self = n.floor().into(),
+            Value::Decimal(n) => *n = n.floor(),
+            Value::Complex(n) => self = Complex64::new(n.re.floor(), n.im.floor()).into(),
+            _ => {}
         }
         self.demote();
         self
     }
 
     /// Raise this value by another.
+    ///
+    /// Integers and fractions raised to negative integral powers produce exact fractions: `2**-2` is `1/4`.
+    /// Non-integral exponents produce floats.
+    /// Integer results which overflow their order are promoted, as for multiplication.
+    /// Powers which have no real result, such as `(-4)**0.5`, are complex.
     pub fn pow(self, right: impl Into<Value>) -> Result {
-        let right = right.into();
-        match self {
-            Value::UnsignedInt(n) => {
-                let right = right.as_u32()?;
-                Ok(n.pow(right).into())
-            }
-            Value::UnsignedBigInt(n) => {
-                let right = right.as_u32()?;
-                Ok(n.pow(right).into())
+        let mut right = right.into();
+        if right.order() == Order::Complex {
+            let mut base = self;
+            let base = *base.promote_to_complex();
+            return Ok(base.powc(*right.promote_to_complex()).into());
+        }
+        if let Value::Complex(base) = self {
+            let exponent = *right.promote_to_float();
+            // integral powers multiply, which keeps `i**2` exactly real
+            if exponent.fract() == 0.0 && exponent.abs() <= f64::from(i32::MAX) {
+                return Ok(base.powi(exponent as i32).into());
             }
-            Value::SignedInt(n) => {
-                let right = right.as_u32()?;
-                Ok(n.pow(right).into())
+            return Ok(base.powf(exponent).into());
+        }
+        // roots such as `x**(1/2)` have no exact representation
+        let integral_exponent =
+            right.order() < Order::Rational || matches!(right, Value::Float(n) if n.fract() == 0.0);
+        if right < Value::UnsignedInt(0) && self.order() != Order::Float {
+            let mut base = self;
+            // an exact number to a negative power is its reciprocal to the positive power
+            if integral_exponent && base.order() <= Order::Rational && base != Value::UnsignedInt(0)
+            {
+                base.promote_to_rational();
+                let Value::Rational(n) = base else {
+                    unreachable!("we just promoted up to rational")
+                };
+                return Value::from(n.recip()).pow(-right);
             }
-            Value::SignedBigInt(n) => {
-                let right = right.as_u32()?;
-                Ok(n.pow(right).into())
+            base.promote_to_float();
+            return base.pow(right);
+        }
+        if !integral_exponent || self.order() == Order::Float {
+            let mut base = self;
+            let base = *base.promote_to_float();
+            let exponent = *right.promote_to_float();
+            let power = base.powf(exponent);
+            if power.is_nan() && !base.is_nan() && !exponent.is_nan() {
+                return Ok(Complex64::from(base).powf(exponent).into());
             }
-            Value::Float(n) => {
-                if let Value::Float(e) = right {
-                    Ok(n.powf(e).into())
-                } else {
-                    let right = right
-                        .as_u32()?
-                        .try_into()
-                        .map_err(|_| ArithmeticError::Overflow)?;
-                    Ok(n.powi(right).into())
+            return Ok(power.into());
+        }
+
+        let exponent = right.as_u32()?;
+        let mut base = self;
+        loop {
+            let power = match &base {
+                Value::UnsignedInt(n) => n.checked_pow(exponent).map(Value::from),
+                Value::UnsignedBigInt(n) => n.checked_pow(exponent).map(Value::from),
+                Value::SignedInt(n) => n.checked_pow(exponent).map(Value::from),
+                Value::SignedBigInt(n) => n.checked_pow(exponent).map(Value::from),
+                Value::ArbitraryInt(n) => {
+                    // the result has at most this many bits
+                    let bits = n.bits().saturating_mul(exponent.into());
+                    (bits <= MAX_ARBITRARY_BITS).then(|| n.pow(exponent).into())
                 }
+                Value::Rational(n) => {
+                    let bits = n.numer().bits().max(n.denom().bits());
+                    (bits.saturating_mul(exponent.into()) <= MAX_ARBITRARY_BITS)
+                        .then(|| Pow::pow(n, exponent).into())
+                }
+                Value::Decimal(n) => n.checked_powu(exponent.into()).map(Value::from),
+                Value::Float(n) => Some(n.powf(exponent.into()).into()),
+                Value::Complex(_) => unreachable!("complex bases have already been handled"),
+            };
+            if let Some(power) = power {
+                return Ok(power);
             }
+            base.promote();
         }
     }
 
     /// Compute the absolute value of this value.
-    pub fn abs(self) -> Value {
+    pub fn abs(mut self) -> Value {
         match self {
             Value::UnsignedInt(n) => n.into(),
             Value::UnsignedBigInt(n) => n.into(),
+            // these integers cannot represent the absolute value of their minima
+            Value::SignedInt(i64::MIN) | Value::SignedBigInt(i128::MIN) => {
+                self.promote();
+                self.abs()
+            }
             Value::SignedInt(n) => n.abs().into(),
             Value::SignedBigInt(n) => n.abs().into(),
+            Value::ArbitraryInt(n) => n.abs().into(),
+            Value::Rational(n) => n.abs().into(),
+            Value::Decimal(n) => n.abs().into(),
             Value::Float(n) => n.abs().into(),
+            Value::Complex(n) => n.norm().into(),
         }
     }
 
+    /// Compute the factorial of a whole number: `5!` is `120`.
+    ///
+    /// The result is exact, unless it would need more than [`MAX_ARBITRARY_BITS`] bits,
+    /// in which case it is an overflow error, as for `**`. The factorial of a float is a float.
+    pub fn factorial(self) -> Result {
+        if self < Value::UnsignedInt(0) {
+            return Err(Error::Domain("factorial".into()));
+        }
+        // any factorial too large for a u32 is far beyond the limit
+        let n = self.as_u32()?;
+        let mut product = BigInt::one();
+        for k in 2..=n {
+            product *= k;
+            if product.bits() > MAX_ARBITRARY_BITS {
+                return Err(ArithmeticError::Overflow.into());
+            }
+        }
+        let mut result = Value::narrowest(product);
+        if self.order() == Order::Float {
+            result.promote_to_float();
+        }
+        Ok(result)
+    }
+
     /// Compute the smallest integer greater than or equal to self.
     pub fn ceil(self) -> Value {
-        if let Value::Float(n) = self {
-            let mut out = Value::from(n.ceil());
-            out.demote();
-            out
-        } else {
-            self
+        match self {
+            Value::Float(n) => {
+                let mut out = Value::from(n.ceil());
+                out.demote();
+                out
+            }
+            Value::Rational(n) => n.ceil().into(),
+            Value::Decimal(n) => {
+                let mut out = Value::from(n.ceil());
+                out.demote();
+                out
+            }
+            Value::Complex(n) => {
+                let mut out = Value::from(Complex64::new(n.re.ceil(), n.im.ceil()));
+                out.demote();
+                out
+            }
+            _ => self,
         }
     }
 
     /// Compute the greatest integer less than or equal to self.
     pub fn floor(self) -> Value {
-        if let Value::Float(n) = self {
-            let mut out = Value::from(n.floor());
-            out.demote();
-            out
-        } else {
-            self
+        match self {
+            Value::Float(n) => {
+                let mut out = Value::from(n.floor());
+                out.demote();
+                out
+            }
+            Value::Rational(n) => n.floor().into(),
+            Value::Decimal(n) => {
+                let mut out = Value::from(n.floor());
+                out.demote();
+                out
+            }
+            Value::Complex(n) => {
+                let mut out = Value::from(Complex64::new(n.re.floor(), n.im.floor()));
+                out.demote();
+                out
+            }
+            _ => self,
         }
     }
 
     /// Round self to the nearest integer; halfway cases away from 0.0.
     pub fn round(self) -> Value {
-        if let Value::Float(n) = self {
-            let mut out = Value::from(n.round());
-            out.demote();
-            out
-        } else {
-            self
+        match self {
+            Value::Float(n) => {
+                let mut out = Value::from(n.round());
+                out.demote();
+                out
+            }
+            Value::Rational(n) => n.round().into(),
+            Value::Decimal(n) => {
+                let mut out = Value::from(
+                    n.round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero),
+                );
+                out.demote();
+                out
+            }
+            Value::Complex(n) => {
+                let mut out = Value::from(Complex64::new(n.re.round(), n.im.round()));
+                out.demote();
+                out
+            }
+            _ => self,
         }
     }
 
     /// Compute the sine of self.
-    pub fn sin(mut self) -> Value {
-        {
-            let f = self.promote_to_float();
-            *f = f.sin();
-        }
-        self
+    pub fn sin(self) -> Value {
+        self.analytic(f64::sin, Complex64::sin)
     }
 
     /// Compute the cosine of self.
-    pub fn cos(mut self) -> Value {
-        {
-            let f = self.promote_to_float();
-            *f = f.cos();
-        }
-        self
+    pub fn cos(self) -> Value {
+        self.analytic(f64::cos, Complex64::cos)
     }
 
     /// Compute the tangent of self.
-    pub fn tan(mut self) -> Value {
-        {
-            let f = self.promote_to_float();
-            *f = f.tan();
-        }
-        self
+    pub fn tan(self) -> Value {
+        self.analytic(f64::tan, Complex64::tan)
     }
 
     /// Compute the hyperbolic sine of self.
-    pub fn sinh(mut self) -> Value {
-        {
-            let f = self.promote_to_float();
-            *f = f.sinh();
-        }
-        self
+    pub fn sinh(self) -> Value {
+        self.analytic(f64::sinh, Complex64::sinh)
     }
 
     /// Compute the hyperbolic cosine of self.
-    pub fn cosh(mut self) -> Value {
-        {
-            let f = self.promote_to_float();
-            *f = f.cosh();
-        }
-        self
+    pub fn cosh(self) -> Value {
+        self.analytic(f64::cosh, Complex64::cosh)
     }
 
     /// Compute the hyperbolic tangent of self.
-    pub fn tanh(mut self) -> Value {
-        {
-            let f = self.promote_to_float();
-            *f = f.tanh();
-        }
-        self
+    pub fn tanh(self) -> Value {
+        self.analytic(f64::tanh, Complex64::tanh)
     }
 
     /// Compute the arcsine of self.
-    pub fn asin(mut self) -> Value {
-        {
-            let f = self.promote_to_float();
-            *f = f.asin();
-        }
-        self
+    pub fn asin(self) -> Value {
+        self.analytic(f64::asin, Complex64::asin)
     }
 
     /// Compute the arccosine of self.
-    pub fn acos(mut self) -> Value {
-        {
-            let f = self.promote_to_float();
-            *f = f.acos();
-        }
-        self
+    pub fn acos(self) -> Value {
+        self.analytic(f64::acos, Complex64::acos)
     }
 
     /// Compute the arctangent of self.
-    pub fn atan(mut self) -> Value {
-        {
-            let f = self.promote_to_float();
-            *f = f.atan();
-        }
-        self
+    pub fn atan(self) -> Value {
+        self.analytic(f64::atan, Complex64::atan)
+    }
+
+    /// Compute the four-quadrant arctangent of self (`y`) and `x`.
+    ///
+    /// This is only defined for real numbers; complex arguments are an error.
+    pub fn atan2(mut self, x: impl Into<Value>) -> Result {
+        let mut x = x.into();
+        let x = *x.try_promote_to_float()?;
+        let f = self.try_promote_to_float()?;
+        *f = f.atan2(x);
+        Ok(self)
     }
 
     /// Compute the inverse hyperbolic sine of self.
-    pub fn asinh(mut self) -> Value {
-        {
-            let f = self.promote_to_float();
-            *f = f.asinh();
-        }
-        self
+    pub fn asinh(self) -> Value {
+        self.analytic(f64::asinh, Complex64::asinh)
     }
 
     /// Compute the inverse hyperbolic cosine of self.
-    pub fn acosh(mut self) -> Value {
-        {
-            let f = self.promote_to_float();
-            *f = f.acosh();
-        }
-        self
+    pub fn acosh(self) -> Value {
+        self.analytic(f64::acosh, Complex64::acosh)
     }
 
     /// Compute the inverse hyperbolic tangent of self.
-    pub fn atanh(mut self) -> Value {
-        {
-            let f = self.promote_to_float();
-            *f = f.atanh();
-        }
-        self
+    pub fn atanh(self) -> Value {
+        self.analytic(f64::atanh, Complex64::atanh)
     }
 
     /// Convert self as degrees to radians.
-    pub fn rad(mut self) -> Value {
-        {
-            let f = self.promote_to_float();
-            *f *= std::f64::consts::PI / 180.0;
-        }
-        self
+    pub fn rad(self) -> Value {
+        self.analytic(
+            |f| f * (std::f64::consts::PI / 180.0),
+            |z| z * (std::f64::consts::PI / 180.0),
+        )
     }
 
     /// Convert self as radians to degrees.
-    pub fn deg(mut self) -> Value {
-        {
-            let f = self.promote_to_float();
-            *f *= 180.0 / std::f64::consts::PI;
-        }
-        self
+    pub fn deg(self) -> Value {
+        self.analytic(
+            |f| f * (180.0 / std::f64::consts::PI),
+            |z| z * (180.0 / std::f64::consts::PI),
+        )
     }
 
     /// Determine the square root of self.
-    pub fn sqrt(mut self) -> Value {
-        {
-            let f = self.promote_to_float();
-            *f = f.sqrt();
-        }
-        self
+    pub fn sqrt(self) -> Value {
+        self.analytic(f64::sqrt, Complex64::sqrt)
+    }
+
+    /// Determine the length of the hypotenuse of a right triangle with legs self and `other`.
+    ///
+    /// Complex legs contribute their magnitudes.
+    pub fn hypot(self, other: impl Into<Value>) -> Value {
+        let mut x = self.abs();
+        let mut y = other.into().abs();
+        x.promote_to_float().hypot(*y.promote_to_float()).into()
     }
 
     /// Determine the cube root of self.
-    pub fn cbrt(mut self) -> Value {
-        {
-            let f = self.promote_to_float();
-            *f = f.cbrt();
-        }
-        self
+    pub fn cbrt(self) -> Value {
+        self.analytic(f64::cbrt, Complex64::cbrt)
     }
 
     /// Determine the base-10 logarithm of self.
-    pub fn log(mut self) -> Value {
+    pub fn log(self) -> Value {
+        self.analytic(f64::log10, Complex64::log10)
+    }
+
+    /// Determine the logarithm of self in an arbitrary base.
+    ///
+    /// Bases 10 and 2 are as precise as [`Value::log`] and [`Value::lg`]. A real base must be
+    /// positive and other than 1; otherwise the result is NaN.
+    pub fn log_base(self, base: impl Into<Value>) -> Value {
+        let base = base.into();
+        if base == Value::UnsignedInt(10) {
+            return self.log();
+        }
+        if base == Value::UnsignedInt(2) {
+            return self.lg();
+        }
+        if base.order() < Order::Complex
+            && (base <= Value::UnsignedInt(0) || base == Value::UnsignedInt(1))
         {
-            let f = self.promote_to_float();
-            *f = f.log10();
+            return Value::Float(f64::NAN);
         }
-        self
+        self.ln() / base.ln()
     }
 
     /// Determine the base-2 logarithm of self
-    pub fn lg(mut self) -> Value {
-        {
-            let f = self.promote_to_float();
-            *f = f.log2();
-        }
-        self
+    pub fn lg(self) -> Value {
+        self.analytic(f64::log2, Complex64::log2)
     }
 
     /// Determine the base-`e` (natural) logarithm of self.
-    pub fn ln(mut self) -> Value {
-        {
-            let f = self.promote_to_float();
-            *f = f.ln();
-        }
-        self
+    pub fn ln(self) -> Value {
+        self.analytic(f64::ln, Complex64::ln)
     }
 
     /// Determine `e**self`
-    pub fn exp(mut self) -> Value {
-        {
-            let f = self.promote_to_float();
-            *f = f.exp();
+    pub fn exp(self) -> Value {
+        self.analytic(f64::exp, Complex64::exp)
+    }
+
+    /// Determine the real part of self.
+    pub fn re(self) -> Value {
+        match self {
+            Value::Complex(n) => n.re.into(),
+            _ => self,
         }
-        self
+    }
+
+    /// Determine the imaginary part of self, which is 0 for real numbers.
+    pub fn im(self) -> Value {
+        match self {
+            Value::Complex(n) => n.im.into(),
+            _ => Value::UnsignedInt(0),
+        }
+    }
+
+    /// Determine the argument of self: its angle from the positive real axis, in radians.
+    pub fn arg(mut self) -> Value {
+        self.promote_to_complex().arg().into()
+    }
+
+    /// Determine the complex conjugate of self.
+    pub fn conj(self) -> Value {
+        match self {
+            Value::Complex(n) => n.conj().into(),
+            _ => self,
+        }
+    }
+
+    /// Apply a function which is analytic on the complex plane.
+    ///
+    /// Real values use `real`, unless it has no real result, as for `sqrt(-1)`;
+    /// then, like complex values, they use `complex`.
+    fn analytic(mut self, real: fn(f64) -> f64, complex: fn(Complex64) -> Complex64) -> Value {
+        if let Value::Complex(z) = self {
+            return complex(z).into();
+        }
+        let x = *self.promote_to_float();
+        let y = real(x);
+        if y.is_nan() && !x.is_nan() {
+            let z = complex(Complex64::from(x));
+            if !z.is_nan() {
+                return z.into();
+            }
+        }
+        y.into()
     }
 }
 
@@ -328,13 +494,65 @@ mod demotion_tests {
         assert_ne!(result.order(), Order::Float);
     }
 
+    // ---------- ABS ----------
+    #[rstest]
+    #[case(i64::MIN, Order::SignedBigInt)]
+    #[case(i128::MIN, Order::ArbitraryInt)]
+    fn abs_minimum_promotes(#[case] val: impl Into<Value>, #[case] expect_order: Order) {
+        let val: Value = val.into();
+        let result = val.clone().abs();
+        assert_eq!(result.order(), expect_order);
+        assert_eq!(result, -val);
+    }
+
+    // ---------- FACTORIAL ----------
+    #[rstest]
+    #[case(0_u64, 1_u64)]
+    #[case(1_u64, 1_u64)]
+    #[case(20_u64, 2_432_902_008_176_640_000_u64)]
+    fn factorial(#[case] val: u64, #[case] expect: u64) {
+        assert_eq!(Value::from(val).factorial().unwrap(), Value::from(expect));
+    }
+
+    #[test]
+    fn factorial_is_exact() {
+        let result = Value::from(40_u64).factorial().unwrap();
+        assert_eq!(result.order(), Order::ArbitraryInt);
+        let expect = (2..=40_u32).fold(BigInt::one(), |product, k| product * k);
+        assert_eq!(result, Value::from(expect));
+    }
+
+    #[rstest]
+    #[case::float(5.0_f64, Value::Float(120.0))]
+    fn factorial_float(#[case] val: impl Into<Value>, #[case] expect: Value) {
+        assert!(val.into().factorial().unwrap().strict_eq(&expect));
+    }
+
+    #[rstest]
+    #[case::beyond_limit(100_000_u64)]
+    #[case::beyond_u32(u64::MAX)]
+    fn factorial_beyond_limit_overflows(#[case] val: u64) {
+        assert!(matches!(
+            Value::from(val).factorial(),
+            Err(Error::Arithmetic(ArithmeticError::Overflow))
+        ));
+    }
+
+    #[rstest]
+    #[case::negative(Value::from(-1_i64))]
+    #[case::fraction(Value::from(1_u64) / 2_u64)]
+    #[case::float(Value::Float(2.5))]
+    fn factorial_of_non_whole_number_fails(#[case] val: Value) {
+        assert!(val.factorial().is_err());
+    }
+
     // ---------- CEIL ----------
     #[rstest]
     fn ceil_integers_remain_integral(
         #[values(10_u64, 20_u128, -30_i64, -40_i128)] val: impl Into<Value>,
     ) {
         let val = val.into();
-        let result = val.ceil();
+        let result = val.clone().ceil();
         assert_eq!(result.order(), val.order());
     }
 
@@ -351,7 +569,7 @@ mod demotion_tests {
         #[values(10_u64, 20_u128, -30_i64, -40_i128)] val: impl Into<Value>,
     ) {
         let val = val.into();
-        let result = val.floor();
+        let result = val.clone().floor();
         assert_eq!(result.order(), val.order());
     }
 
@@ -368,7 +586,7 @@ mod demotion_tests {
         #[values(10_u64, 20_u128, -30_i64, -40_i128)] val: impl Into<Value>,
     ) {
         let val = val.into();
-        let result = val.round();
+        let result = val.clone().round();
         assert_eq!(result.order(), val.order());
     }
 
@@ -379,6 +597,163 @@ mod demotion_tests {
         assert_ne!(result.order(), Order::Float);
     }
 
+    // ---------- POW ----------
+    #[rstest]
+    fn pow_negative_exponent_is_exact(
+        #[values(2_u64, 2_u128, 2_i64, 2_i128)] base: impl Into<Value>,
+        #[values(-1_i64, -1_i128, -1.0_f64)] exponent: impl Into<Value>,
+    ) {
+        let result = base.into().pow(exponent).unwrap();
+        assert!(result.strict_eq(&(Value::from(1_u64) / 2_u64)));
+    }
+
+    #[rstest]
+    #[case(Value::from(1_u64) / 3_u64, -1, Value::from(3_u64))]
+    #[case(Value::from(2_u64) / 3_u64, -2, Value::from(9_u64) / 4_u64)]
+    #[case(Value::from(-2_i64), -3, Value::from(-1_i64) / 8_u64)]
+    fn pow_negative_exponent_inverts(
+        #[case] base: Value,
+        #[case] exponent: i64,
+        #[case] expect: Value,
+    ) {
+        assert!(base.pow(exponent).unwrap().strict_eq(&expect));
+    }
+
+    #[test]
+    fn pow_negative_exponent_of_float_is_float() {
+        let result = Value::Float(2.0).pow(-1_i64).unwrap();
+        assert!(result.strict_eq(&Value::Float(0.5)));
+    }
+
+    #[test]
+    fn pow_overflow_is_exact() {
+        let result = Value::from(2_u64).pow(200_u64).unwrap();
+        assert_eq!(result.order(), Order::ArbitraryInt);
+        assert_eq!(result, Value::from(num_bigint::BigInt::from(1) << 200));
+    }
+
+    #[rstest]
+    fn pow_overflow_is_exact_in_each_order(
+        #[values(3_u64, 3_u128, -3_i64, -3_i128)] base: impl Into<Value>
+            + Into<num_bigint::BigInt>
+            + Copy,
+        #[values(41, 81, 100)] exponent: u32,
+    ) {
+        let result = Into::<Value>::into(base).pow(exponent).unwrap();
+        let expect = Pow::pow(Into::<num_bigint::BigInt>::into(base), exponent);
+        assert_eq!(result, Value::from(expect));
+    }
+
+    #[rstest]
+    #[case(2_u64, 64, Order::UnsignedBigInt)]
+    #[case(3_i64, 40, Order::SignedBigInt)]
+    #[case(-2_i64, 127, Order::SignedBigInt)]
+    #[case(-2_i64, 128, Order::ArbitraryInt)]
+    fn pow_promotes_through_orders(
+        #[case] base: impl Into<Value>,
+        #[case] exponent: u32,
+        #[case] expect_order: Order,
+    ) {
+        let result = base.into().pow(exponent).unwrap();
+        assert_eq!(result.order(), expect_order);
+    }
+
+    #[test]
+    fn pow_beyond_arbitrary_limit_is_float() {
+        let result = Value::from(10_u64).pow(10_u64.pow(9)).unwrap();
+        assert!(matches!(result, Value::Float(f) if f.is_infinite()));
+    }
+
+    #[test]
+    fn pow_limit_bounds_result() {
+        // 3**200000 has about 317000 bits
+        let result = Value::from(3_u64).pow(200_000_u64).unwrap();
+        assert!(matches!(result, Value::Float(f) if f.is_infinite()));
+    }
+
+    #[test]
+    fn pow_of_fraction_is_exact() {
+        let two_thirds = Value::from(2_u64) / 3_u64;
+        let result = two_thirds.pow(3_u64).unwrap();
+        assert_eq!(result, Value::from(8_u64) / 27_u64);
+        assert_eq!(result.order(), Order::Rational);
+    }
+
+    #[rstest]
+    fn pow_non_integral_exponent_is_float(
+        #[values(4_u64, 4_i128, 4.0_f64)] base: impl Into<Value>,
+    ) {
+        let half = Value::from(1_u64) / 2_u64;
+        let result = base.into().pow(half).unwrap();
+        assert!(result.strict_eq(&Value::Float(2.0)));
+    }
+
+    // ---------- FRACTIONS ----------
+    #[rstest]
+    #[case(7, 2, 4, 3, 4)]
+    #[case(-7, 2, -3, -4, -4)]
+    #[case(1, 3, 1, 0, 0)]
+    fn fraction_rounding(
+        #[case] numer: i64,
+        #[case] denom: i64,
+        #[case] ceil: i64,
+        #[case] floor: i64,
+        #[case] round: i64,
+    ) {
+        let val = Value::from(numer) / denom;
+        for (result, expect) in [
+            (val.clone().ceil(), ceil),
+            (val.clone().floor(), floor),
+            (val.round(), round),
+        ] {
+            assert_eq!(result, expect.into());
+            assert!(result.order() < Order::Rational, "{result:?}");
+        }
+    }
+
+    // ---------- DECIMALS ----------
+    #[rstest]
+    #[case("2.5", 3, 2, 3)]
+    #[case("-2.5", -2, -3, -3)]
+    #[case("0.125", 1, 0, 0)]
+    fn decimal_rounding(
+        #[case] input: &str,
+        #[case] ceil: i64,
+        #[case] floor: i64,
+        #[case] round: i64,
+    ) {
+        let val = Value::Decimal(input.parse().unwrap());
+        for (result, expect) in [
+            (val.clone().ceil(), ceil),
+            (val.clone().floor(), floor),
+            (val.round(), round),
+        ] {
+            assert_eq!(result, expect.into());
+            assert!(result.order() < Order::Rational, "{result:?}");
+        }
+    }
+
+    #[test]
+    fn decimal_trunc_div_is_integer() {
+        let val = Value::Decimal("10.50".parse().unwrap());
+        let result = val.trunc_div(Value::Decimal("0.25".parse().unwrap()));
+        assert!(result.strict_eq(&Value::UnsignedInt(42)));
+    }
+
+    #[test]
+    fn decimal_pow_is_exact() {
+        let val = Value::Decimal("1.1".parse().unwrap());
+        let result = val.pow(2_u64).unwrap();
+        assert!(result.strict_eq(&Value::Decimal("1.21".parse().unwrap())));
+    }
+
+    #[test]
+    fn trunc_div_integers_is_exact() {
+        let big: Value = (u128::MAX - 1).into();
+        let result = big.trunc_div(3_u64);
+        assert!(result.strict_eq(&Value::UnsignedBigInt((u128::MAX - 1) / 3)));
+    }
+
     // ---------- EDGE CASES ----------
     #[test]
     fn ceil_of_large_float_demotes_to_bigint() {
@@ -455,4 +830,114 @@ mod demotion_tests {
         let result = left.trunc_div(right);
         assert!(matches!(result, Value::SignedInt(_)));
     }
+
+    // ---------- LOG ----------
+    #[rstest]
+    #[case(1000_u64, 10_u64, 3.0)]
+    #[case(8_u64, 2_u64, 3.0)]
+    #[case(0.001_f64, 10.0_f64, -3.0)]
+    #[case(81_u64, 3_u64, 4.0)]
+    fn log_base(#[case] x: impl Into<Value>, #[case] base: impl Into<Value>, #[case] expect: f64) {
+        assert_eq!(x.into().log_base(base), Value::Float(expect));
+    }
+
+    #[rstest]
+    fn log_base_out_of_domain_is_nan(
+        #[values(Value::from(1_u64), Value::from(0_u64), Value::from(-2_i64), Value::Float(-0.5))]
+        base: Value,
+    ) {
+        assert!(matches!(Value::from(8_u64).log_base(base), Value::Float(n) if n.is_nan()));
+    }
+
+    // ---------- COMPLEX ----------
+    #[rstest]
+    #[case::sqrt(Value::sqrt, -4.0, Complex64::new(0.0, 2.0))]
+    #[case::ln(Value::ln, -1.0, Complex64::new(0.0, std::f64::consts::PI))]
+    #[case::log(Value::log, -100.0, Complex64::new(2.0, std::f64::consts::PI / std::f64::consts::LN_10))]
+    #[case::asin(Value::asin, 2.0, Complex64::new(2.0, 0.0).asin())]
+    #[case::acosh(Value::acosh, 0.0, Complex64::new(0.0, std::f64::consts::FRAC_PI_2))]
+    fn real_domain_errors_are_complex(
+        #[case] function: fn(Value) -> Value,
+        #[case] input: f64,
+        #[case] expect: Complex64,
+    ) {
+        let result = function(input.into());
+        let Value::Complex(z) = result else {
+            panic!("expected a complex result, got {result:?}");
+        };
+        assert!((z - expect).norm() < 1e-12, "{z} != {expect}");
+    }
+
+    #[rstest]
+    fn real_functions_of_reals_stay_real(
+        #[values(Value::sqrt, Value::ln, Value::asin, Value::exp, Value::cbrt)]
+        function: fn(Value) -> Value,
+    ) {
+        let result = function(Value::from(1_u64) / 2_u64);
+        assert_eq!(result.order(), Order::Float);
+    }
+
+    #[test]
+    fn nan_stays_nan() {
+        let result = Value::from(f64::NAN).sqrt();
+        assert!(matches!(result, Value::Float(n) if n.is_nan()));
+    }
+
+    #[test]
+    fn complex_pow() {
+        let i_squared = Value::I.pow(2_u64).unwrap();
+        assert!(i_squared.strict_eq(&Value::Float(-1.0)));
+        let inverse = Value::I.pow(-1_i64).unwrap();
+        assert!(inverse.strict_eq(&Complex64::new(0.0, -1.0).into()));
+        let root = Value::from(-4_i64).pow(0.5_f64).unwrap();
+        let Value::Complex(z) = root else {
+            panic!("expected a complex result, got {root:?}");
+        };
+        assert!((z - Complex64::new(0.0, 2.0)).norm() < 1e-12);
+    }
+
+    #[test]
+    fn complex_parts() {
+        let z = Value::from(Complex64::new(3.0, 4.0));
+        assert!(z.clone().re().strict_eq(&Value::Float(3.0)));
+        assert!(z.clone().im().strict_eq(&Value::Float(4.0)));
+        assert!(z.clone().abs().strict_eq(&Value::Float(5.0)));
+        assert!(z
+            .clone()
+            .conj()
+            .strict_eq(&Complex64::new(3.0, -4.0).into()));
+        assert_eq!(z.arg(), Value::from(4.0_f64.atan2(3.0)));
+        assert!(Value::from(7_u64).im().strict_eq(&Value::UnsignedInt(0)));
+        assert_eq!(Value::from(-1_i64).arg(), Value::PI);
+    }
+
+    #[test]
+    fn complex_atan2_and_hypot() {
+        assert!(matches!(
+            Value::I.atan2(1_u64),
+            Err(Error::ImproperlyComplex)
+        ));
+        assert!(matches!(
+            Value::from(1_u64).atan2(Value::I),
+            Err(Error::ImproperlyComplex)
+        ));
+        let real = Value::from(Complex64::new(1.0, 0.0)).atan2(1_u64).unwrap();
+        assert_eq!(real, Value::from(std::f64::consts::FRAC_PI_4));
+        let hypot = Value::I.hypot(1_u64);
+        assert_eq!(hypot, Value::from(std::f64::consts::SQRT_2));
+    }
+
+    #[test]
+    fn complex_rounding() {
+        let z = Value::from(Complex64::new(2.5, -1.5));
+        assert!(z
+            .clone()
+            .round()
+            .strict_eq(&Complex64::new(3.0, -2.0).into()));
+        assert!(z
+            .clone()
+            .floor()
+            .strict_eq(&Complex64::new(2.0, -2.0).into()));
+        assert!(z.ceil().strict_eq(&Complex64::new(3.0, -1.0).into()));
+    }
 }
@@ -1,6 +1,9 @@
 use std::str::FromStr;
 
-use super::Result;
+use num_bigint::BigInt;
+use rust_decimal::Decimal;
+
+use super::{Order, Result};
 use crate::{ParseValueError, Value};
 
 /// Strip underscores and leading bit markers from the input string
@@ -10,6 +13,30 @@ fn clean_input(s: &str, leading: &str) -> String {
     input.trim_start_matches(leading).to_owned()
 }
 
+/// Compute `digits * 10**exponent` exactly, if it is an integer which fits into a fixed-width integer order.
+fn exact_integer(digits: &str, exponent: i32) -> Option<Value> {
+    // no integer order holds more than 39 decimal digits
+    const MAX_DIGITS: u32 = 39;
+
+    let mut digits = digits.to_owned();
+    if exponent >= 0 {
+        if exponent.unsigned_abs() > MAX_DIGITS {
+            return None;
+        }
+        digits.extend(std::iter::repeat_n('0', exponent as usize));
+    } else {
+        let keep = digits.len().checked_sub(exponent.unsigned_abs() as usize)?;
+        if !digits[keep..].bytes().all(|b| b == b'0') {
+            return None;
+        }
+        digits.truncate(keep);
+    }
+
+    Value::from_str_radix(&digits, 10)
+        .ok()
+        .filter(|value| value.order() < Order::ArbitraryInt)
+}
+
 impl Value {
     /// Parses an integer from a string slice with digits in a given base.
     ///
@@ -24,7 +51,15 @@ impl Value {
             .or_else(|_| u128::from_str_radix(src, radix).map(Self::UnsignedBigInt))
             .or_else(|_| i64::from_str_radix(src, radix).map(Self::SignedInt))
             .or_else(|_| i128::from_str_radix(src, radix).map(Self::SignedBigInt))
-            .map_err(|_| ParseValueError::Radix(src.to_owned(), radix))
+            .ok()
+            // `BigInt` would accept underscores, but they are an error here
+            .or_else(|| {
+                (!src.contains('_'))
+                    .then(|| BigInt::parse_bytes(src.as_bytes(), radix))
+                    .flatten()
+                    .map(Self::ArbitraryInt)
+            })
+            .ok_or_else(|| ParseValueError::Radix(src.to_owned(), radix))
     }
 
     /// Parse a binary input without decimals.
@@ -41,11 +76,55 @@ impl Value {
         Value::from_str_radix(&clean_input(s, "0o"), 8)
     }
 
-    /// Parse a decimal input which may or may not contain a decimal point.
+    /// Parse a decimal input which may or may not contain a decimal point or an exponent.
     ///
     /// Should succeed with or without a leading `0d`.
+    ///
+    /// - Inputs with a decimal point are floats: `1.5`, `.25`, `6.022e23`.
+    /// - Inputs without a decimal point or exponent are integers: `1_000`.
+    /// - Inputs without a decimal point but with an exponent are integers if their value is
+    ///   exactly integral and fits into an integer order (`1e3`, `250e-1`), and floats
+    ///   otherwise (`1E-9`, `1e100`).
     pub fn parse_decimal(s: &str) -> Result<Self, ParseValueError> {
-        Value::from_str_radix(&clean_input(s, "0d"), 10)
+        let input = clean_input(s, "0d");
+        let parse_float = || {
+            input
+                .parse::<f64>()
+                .map(Self::Float)
+                .map_err(|_| ParseValueError::Simple(s.to_owned()))
+        };
+
+        if input.contains('.') {
+            return parse_float();
+        }
+
+        match input.split_once(['e', 'E']) {
+            None => Value::from_str_radix(&input, 10),
+            Some((mantissa, exponent)) => match exponent.parse::<i32>() {
+                Ok(exponent) => exact_integer(mantissa, exponent).map_or_else(parse_float, Ok),
+                // an exponent beyond `i32` saturates to infinity or 0, as it does for a float
+                Err(_) => parse_float(),
+            },
+        }
+    }
+
+    /// Parse a decimal input as by [`Value::parse_decimal`], but keep non-integral inputs exact.
+    ///
+    /// Inputs which [`Value::parse_decimal`] would make floats are base-10 decimals instead,
+    /// as long as they fit into 96 bits of mantissa; otherwise they are still floats.
+    pub fn parse_decimal_exact(s: &str) -> Result<Self, ParseValueError> {
+        let value = Value::parse_decimal(s)?;
+        if value.order() != Order::Float {
+            return Ok(value);
+        }
+
+        let input = clean_input(s, "0d");
+        let decimal = if input.contains(['e', 'E']) {
+            Decimal::from_scientific(&input)
+        } else {
+            Decimal::from_str_exact(&input)
+        };
+        Ok(decimal.map_or(value, Self::Decimal))
     }
 
     /// Parse an octal input without decimals.
@@ -65,7 +144,119 @@ impl FromStr for Value {
             .or_else(|_| s.parse::<u128>().map(Self::UnsignedBigInt))
             .or_else(|_| s.parse::<i64>().map(Self::SignedInt))
             .or_else(|_| s.parse::<i128>().map(Self::SignedBigInt))
+            .or_else(|_| s.parse::<BigInt>().map(Self::ArbitraryInt))
             .or_else(|_| s.parse::<f64>().map(Self::Float))
             .map_err(|_| ParseValueError::Simple(s.to_owned()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value::Order;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("1.5", 1.5)]
+    #[case(".25", 0.25)]
+    #[case("0.1", 0.1)]
+    #[case("2.0", 2.0)]
+    #[case("6.022e23", 6.022e23)]
+    #[case("6.022E23", 6.022e23)]
+    #[case("1E-9", 1e-9)]
+    #[case("2.5e-1", 0.25)]
+    #[case("1_000.000_1", 1000.0001)]
+    #[case("1.5e3", 1500.0)]
+    #[case("1e400", f64::INFINITY)]
+    #[case("1e99999999999", f64::INFINITY)]
+    #[case("1e-99999999999", 0.0)]
+    #[case("0e99999999999", 0.0)]
+    fn decimal_floats(#[case] input: &str, #[case] expect: f64) {
+        let value = Value::parse_decimal(input).unwrap();
+        assert!(
+            value.strict_eq(&Value::Float(expect)),
+            "{input} => {value:?}"
+        );
+    }
+
+    #[rstest]
+    #[case("1e3", 1_000)]
+    #[case("1E+3", 1_000)]
+    #[case("1_000e0", 1_000)]
+    #[case("250e-1", 25)]
+    #[case("0d12e2", 1_200)]
+    fn exact_exponents_are_integers(#[case] input: &str, #[case] expect: u64) {
+        let value = Value::parse_decimal(input).unwrap();
+        assert!(
+            value.strict_eq(&Value::UnsignedInt(expect)),
+            "{input} => {value:?}"
+        );
+    }
+
+    #[test]
+    fn exponent_overflowing_u64_uses_wider_integer() {
+        let value = Value::parse_decimal("1e20").unwrap();
+        assert!(value.strict_eq(&Value::UnsignedBigInt(10_u128.pow(20))));
+    }
+
+    #[rstest]
+    fn inexact_or_huge_exponents_are_floats(
+        #[values("1e-3", "15e-1", "1e39", "1e100")] input: &str,
+    ) {
+        let value = Value::parse_decimal(input).unwrap();
+        assert_eq!(value.order(), Order::Float, "{input} => {value:?}");
+    }
+
+    #[rstest]
+    #[case::decimal("340282366920938463463374607431768211456", 10)]
+    #[case::negative("-170141183460469231731687303715884105729", 10)]
+    #[case::hex("1_0000_0000_0000_0000_0000_0000_0000_0000", 16)]
+    fn integers_beyond_i128_are_arbitrary(#[case] input: &str, #[case] radix: u32) {
+        let value = match radix {
+            10 => Value::parse_decimal(input),
+            _ => Value::parse_hex(input),
+        }
+        .unwrap();
+        let expect = BigInt::parse_bytes(input.replace('_', "").as_bytes(), radix).unwrap();
+        assert!(
+            value.strict_eq(&Value::ArbitraryInt(expect)),
+            "{input} => {value:?}"
+        );
+    }
+
+    #[test]
+    fn underscores_are_rejected_by_from_str_radix() {
+        let input = "340_282_366_920_938_463_463_374_607_431_768_211_456";
+        assert!(Value::from_str_radix(input, 10).is_err());
+    }
+
+    #[rstest]
+    fn integers_without_point_or_exponent(#[values("0", "1_000", "0d1234")] input: &str) {
+        let value = Value::parse_decimal(input).unwrap();
+        assert_eq!(value.order(), Order::UnsignedInt);
+    }
+
+    #[rstest]
+    #[case("0.1", "0.1")]
+    #[case("19.99", "19.99")]
+    #[case("4.00", "4.00")]
+    #[case("1_000.000_1", "1000.0001")]
+    #[case("2.5e-1", "0.25")]
+    #[case("1E-9", "0.000000001")]
+    fn exact_decimals(#[case] input: &str, #[case] expect: &str) {
+        let value = Value::parse_decimal_exact(input).unwrap();
+        assert!(
+            value.strict_eq(&Value::Decimal(expect.parse().unwrap())),
+            "{input} => {value:?}"
+        );
+    }
+
+    #[rstest]
+    #[case("1e3", Order::UnsignedInt)]
+    #[case("1e100", Order::Float)]
+    #[case("0.1234567890123456789012345678901234", Order::Float)]
+    fn exact_decimals_keep_other_orders(#[case] input: &str, #[case] expect: Order) {
+        let value = Value::parse_decimal_exact(input).unwrap();
+        assert_eq!(value.order(), expect, "{input} => {value:?}");
+    }
+}
@@ -0,0 +1,588 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    time::{Duration, SystemTime},
+};
+
+use num_runtime_fmt::NumFmt;
+use num_traits::ToPrimitive as _;
+
+use super::{datetime, units, ArithmeticError, Dimension, Error, Result, Value};
+use crate::{
+    ast::{Constant, Function, InfixOperator, PrefixOperator},
+    types::Calcable,
+    Backend, MachineType,
+};
+
+/// A [`Value`] with a physical dimension, such as `3 m` or `5 kW`.
+///
+/// Quantities are stored in SI base units, so `3 km` is `3000 m`. Evaluate expressions in a
+/// `Context<Quantity>` to use units: each unit, with or without an SI prefix, is a name which
+/// evaluates to a quantity of that size, so `3 m / 2 s` is a speed.
+///
+/// Adding, subtracting, or comparing quantities requires that their dimensions match,
+/// as do functions such as `min`. Transcendental functions such as `sin` and `exp`, exponents,
+/// and bitwise operations require pure numbers.
+///
+/// Units of absolute temperature such as `degC` have an offset from absolute zero, which
+/// applies when a number is multiplied by the unit itself: `20 degC` is `293.15 K`.
+///
+/// A date is an instant in time, stored as seconds since the Unix epoch. Dates can be compared,
+/// subtracted from one another to give a duration, and offset by a time, but nothing else.
+/// Durations are ordinary quantities of time which are displayed like `3h 20m`.
+#[derive(Debug, Clone)]
+pub struct Quantity {
+    pub value: Value,
+    pub dimension: Dimension,
+    /// For a unit of absolute temperature, the amount which is added to a number before it is
+    /// multiplied by this unit.
+    pub(super) offset: Option<Value>,
+    /// For a quantity of time, whether it is a date or a duration.
+    time: Option<Time>,
+}
+
+/// What a quantity of time stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Time {
+    /// A point in time, as seconds since the Unix epoch.
+    Instant,
+    /// A length of time, which is displayed in days, hours, minutes, and seconds.
+    Duration,
+}
+
+impl Quantity {
+    /// Create a quantity of `value` base units of `dimension`.
+    pub fn new(value: Value, dimension: Dimension) -> Self {
+        Self {
+            value,
+            dimension,
+            offset: None,
+            time: None,
+        }
+    }
+
+    /// Create a quantity of time of `seconds` seconds.
+    fn time(seconds: Value, time: Time) -> Self {
+        Self {
+            time: Some(time),
+            ..Self::new(seconds, Dimension::SECOND)
+        }
+    }
+
+    /// Create a date `seconds` seconds from the epoch,
+    /// or an error if it is not finite or too far from the epoch to have a year.
+    fn instant(seconds: Value) -> Result<Self> {
+        if datetime::seconds_to_nanos(&seconds)
+            .and_then(datetime::format_datetime)
+            .is_none()
+        {
+            return Err(Error::InvalidDate(seconds.to_string()));
+        }
+        Ok(Self::time(seconds, Time::Instant))
+    }
+
+    /// Whether this quantity is a date, rather than an amount.
+    pub fn is_instant(&self) -> bool {
+        self.time == Some(Time::Instant)
+    }
+
+    fn is_duration(&self) -> bool {
+        self.time == Some(Time::Duration)
+    }
+
+    /// Render this quantity as a date or duration, if it is one.
+    fn format_time(&self) -> Option<String> {
+        let nanos = datetime::seconds_to_nanos(&self.value)?;
+        match self.time? {
+            Time::Instant => datetime::format_datetime(nanos),
+            Time::Duration => Some(datetime::format_duration(nanos)),
+        }
+    }
+}
+
+impl PartialEq for Quantity {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+            && self.dimension == other.dimension
+            && self.is_instant() == other.is_instant()
+    }
+}
+
+impl From<Value> for Quantity {
+    fn from(value: Value) -> Self {
+        Self::new(value, Dimension::default())
+    }
+}
+
+impl From<bool> for Quantity {
+    fn from(b: bool) -> Self {
+        Value::from(b).into()
+    }
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(time) = self.format_time() {
+            return f.write_str(&time);
+        }
+        write!(f, "{}", self.value)?;
+        if !self.dimension.is_dimensionless() {
+            write!(f, " {}", self.dimension)?;
+        }
+        Ok(())
+    }
+}
+
+impl Quantity {
+    /// Append this quantity's unit to its formatted value.
+    fn with_unit(&self, value: String) -> String {
+        if self.dimension.is_dimensionless() {
+            value
+        } else {
+            format!("{value} {}", self.dimension)
+        }
+    }
+}
+
+/// The dimension shared by `left` and `right`, or an error if they differ.
+fn same(left: Dimension, right: Dimension) -> Result<Dimension> {
+    if left == right {
+        Ok(left)
+    } else {
+        Err(Error::DimensionMismatch(left, right))
+    }
+}
+
+/// An error unless `dimension` is that of a pure number.
+fn dimensionless(dimension: Dimension) -> Result<Dimension> {
+    same(dimension, Dimension::default())
+}
+
+/// Apply an arithmetic operator to operands of which at least one is a date.
+fn instant_infix(
+    operator: InfixOperator,
+    left: Quantity,
+    right: Quantity,
+    backend: Backend,
+) -> Result<Quantity> {
+    let is_time = |q: &Quantity| q.dimension == Dimension::SECOND && !q.is_instant();
+    let time = match (operator, left.is_instant(), right.is_instant()) {
+        (InfixOperator::Sub, true, true) => Time::Duration,
+        (InfixOperator::Add | InfixOperator::Sub, true, false) if is_time(&right) => Time::Instant,
+        (InfixOperator::Add, false, true) if is_time(&left) => Time::Instant,
+        _ => return Err(Error::DateArithmetic),
+    };
+    let value = Value::infix(operator, left.value, right.value, backend)?;
+    match time {
+        Time::Instant => Quantity::instant(value),
+        Time::Duration => Ok(Quantity::time(value, time)),
+    }
+}
+
+/// The value of an exponent as an exact fraction `(numer, denom)`, if it has one.
+fn rational_exponent(value: &Value) -> Option<(i64, i64)> {
+    match value {
+        Value::UnsignedInt(n) => Some((i64::try_from(*n).ok()?, 1)),
+        Value::UnsignedBigInt(n) => Some((i64::try_from(*n).ok()?, 1)),
+        Value::SignedInt(n) => Some((*n, 1)),
+        Value::SignedBigInt(n) => Some((i64::try_from(*n).ok()?, 1)),
+        Value::ArbitraryInt(n) => Some((n.to_i64()?, 1)),
+        Value::Rational(n) => Some((n.numer().to_i64()?, n.denom().to_i64()?)),
+        Value::Decimal(n) if n.fract().is_zero() => Some((n.to_i64()?, 1)),
+        Value::Float(n) if n.fract() == 0.0 => Some((n.to_i64()?, 1)),
+        Value::Decimal(_) | Value::Float(_) | Value::Complex(_) => None,
+    }
+}
+
+impl Calcable for Quantity {
+    fn parse(literal: &str, radix: u32, backend: Backend) -> Result<Self> {
+        Value::parse(literal, radix, backend).map(Self::from)
+    }
+
+    fn constant(constant: Constant) -> Result<Self> {
+        let dimension = constant
+            .unit()
+            .map_or(Ok(Dimension::default()), str::parse)
+            .expect("built-in constants are measured in known units");
+        Ok(Self::new(Value::constant(constant)?, dimension))
+    }
+
+    fn unit(name: &str) -> Option<Self> {
+        units::lookup(name)
+    }
+
+    fn datetime(time: SystemTime) -> Result<Self> {
+        let seconds = datetime::nanos_to_seconds(datetime::to_nanos(time));
+        Ok(Self::time(seconds, Time::Instant))
+    }
+
+    fn duration(duration: Duration) -> Result<Self> {
+        let seconds = datetime::nanos_to_seconds(duration.as_nanos() as i128);
+        Ok(Self::time(seconds, Time::Duration))
+    }
+
+    fn prefix(operator: PrefixOperator, operand: Self, backend: Backend) -> Result<Self> {
+        if operand.is_instant() {
+            return Err(Error::DateArithmetic);
+        }
+        let dimension = match operator {
+            PrefixOperator::Negation => operand.dimension,
+            PrefixOperator::Not => dimensionless(operand.dimension)?,
+        };
+        Ok(Self {
+            time: operand.time,
+            ..Self::new(Value::prefix(operator, operand.value, backend)?, dimension)
+        })
+    }
+
+    fn infix(
+        operator: InfixOperator,
+        mut left: Self,
+        right: Self,
+        backend: Backend,
+    ) -> Result<Self> {
+        if left.is_instant() || right.is_instant() {
+            return instant_infix(operator, left, right, backend);
+        }
+        if let (InfixOperator::Mul, Some(offset)) = (operator, &right.offset) {
+            if left.dimension.is_dimensionless() {
+                left.value = Value::infix(InfixOperator::Add, left.value, offset.clone(), backend)?;
+            }
+        }
+        let overflow = || Error::from(ArithmeticError::Overflow);
+        let dimension = match operator {
+            InfixOperator::Add | InfixOperator::Sub | InfixOperator::Rem => {
+                same(left.dimension, right.dimension)?
+            }
+            InfixOperator::Mul => left
+                .dimension
+                .checked_mul(right.dimension)
+                .ok_or_else(overflow)?,
+            InfixOperator::Div | InfixOperator::TruncDiv => left
+                .dimension
+                .checked_div(right.dimension)
+                .ok_or_else(overflow)?,
+            InfixOperator::Pow => {
+                dimensionless(right.dimension)?;
+                if left.dimension.is_dimensionless() {
+                    left.dimension
+                } else {
+                    rational_exponent(&right.value)
+                        .and_then(|(numer, denom)| left.dimension.checked_pow(numer, denom))
+                        .ok_or(Error::FractionalDimension(left.dimension))?
+                }
+            }
+            _ => {
+                dimensionless(right.dimension)?;
+                dimensionless(left.dimension)?
+            }
+        };
+        let duration =
+            dimension == Dimension::SECOND && (left.is_duration() || right.is_duration());
+        let value = Value::infix(operator, left.value, right.value, backend)?;
+        Ok(if duration {
+            Self::time(value, Time::Duration)
+        } else {
+            Self::new(value, dimension)
+        })
+    }
+
+    fn function(function: Function, args: Vec<Self>, backend: Backend) -> Result<Self> {
+        if args.iter().any(Self::is_instant) {
+            return match function {
+                // the number of seconds since the epoch
+                Function::Unix => Ok(args[0].value.clone().into()),
+                Function::Min | Function::Max | Function::Clamp
+                    if args.iter().all(Self::is_instant) =>
+                {
+                    let args = args.into_iter().map(|arg| arg.value).collect();
+                    Ok(Self::time(
+                        Value::function(function, args, backend)?,
+                        Time::Instant,
+                    ))
+                }
+                _ => Err(Error::DateArithmetic),
+            };
+        }
+        if function == Function::Unix {
+            dimensionless(args[0].dimension)?;
+            return Self::instant(args[0].value.clone());
+        }
+        let duration = args.iter().any(Self::is_duration);
+        let first = args[0].dimension;
+        let root = |n| {
+            first
+                .checked_pow(1, n)
+                .ok_or(Error::FractionalDimension(first))
+        };
+        let dimension = match function {
+            Function::Abs
+            | Function::Ceil
+            | Function::Floor
+            | Function::Round
+            | Function::Re
+            | Function::Im
+            | Function::Conj => first,
+            Function::Sqrt => root(2)?,
+            Function::Cbrt => root(3)?,
+            Function::Min | Function::Max | Function::Clamp | Function::Hypot => args
+                .iter()
+                .try_fold(first, |dimension, arg| same(dimension, arg.dimension))?,
+            Function::Atan2 => {
+                same(first, args[1].dimension)?;
+                Dimension::default()
+            }
+            Function::Arg => Dimension::default(),
+            _ => args
+                .iter()
+                .try_fold(first, |_, arg| dimensionless(arg.dimension))?,
+        };
+        let args = args.into_iter().map(|arg| arg.value).collect();
+        let value = Value::function(function, args, backend)?;
+        Ok(if duration && dimension == Dimension::SECOND {
+            Self::time(value, Time::Duration)
+        } else {
+            Self::new(value, dimension)
+        })
+    }
+
+    fn numeric_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.dimension != other.dimension || self.is_instant() != other.is_instant() {
+            return None;
+        }
+        self.value.numeric_cmp(&other.value)
+    }
+
+    fn is_truthy(&self) -> bool {
+        self.value.is_truthy()
+    }
+
+    fn format(&self, format: &NumFmt) -> Result<String, num_runtime_fmt::Error> {
+        if let Some(time) = self.format_time() {
+            return Ok(time);
+        }
+        Ok(self.with_unit(self.value.format(format)?))
+    }
+
+    fn format_as(
+        &self,
+        format: &NumFmt,
+        ty: MachineType,
+    ) -> Result<String, num_runtime_fmt::Error> {
+        if let Some(time) = self.format_time() {
+            return Ok(time);
+        }
+        Ok(self.with_unit(self.value.format_as(format, ty)?))
+    }
+
+    fn format_polar(&self, format: &NumFmt) -> Result<String, num_runtime_fmt::Error> {
+        if let Some(time) = self.format_time() {
+            return Ok(time);
+        }
+        Ok(self.with_unit(self.value.format_polar(format)?))
+    }
+
+    fn convert(self, unit: Self) -> Result<Self> {
+        if unit.is_instant() {
+            return Err(Error::DateArithmetic);
+        }
+        same(self.dimension, unit.dimension)?;
+        // conversion is exact whatever the context's backend, as units are not literals
+        let backend = Backend::default();
+        let mut value = Value::infix(InfixOperator::Div, self.value, unit.value, backend)?;
+        if let Some(offset) = unit.offset {
+            value = Value::infix(InfixOperator::Sub, value, offset, backend)?;
+        }
+        // a converted quantity is for display, in which `340/9 degC` would be unhelpful
+        Ok(value.fraction_to_decimal().into())
+    }
+
+    fn cast(self, ty: MachineType) -> Result<Self> {
+        if self.is_instant() {
+            return Err(Error::DateArithmetic);
+        }
+        Ok(Self {
+            value: self.value.cast(ty)?,
+            ..self
+        })
+    }
+
+    fn lost_precision(operands: &[Self], result: &Self) -> bool {
+        Value::lost_precision(operands.iter().map(|operand| &operand.value), &result.value)
+    }
+
+    fn finish(self, backend: Backend) -> Self {
+        Self {
+            value: self.value.finish(backend),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Context;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("3 m / 2 s", "3/2 m s^-1")]
+    #[case("5 kW * 3 h", "54000000 J")]
+    #[case("2 km + 300 m", "2300 m")]
+    #[case("1 kWh / 1 h", "1000 W")]
+    #[case("(4 m**2)**(1/2)", "2 m")]
+    #[case("sqrt(9 m**2)", "3 m")]
+    #[case("max(1 km, 200 m) == 1000 m", "1")]
+    #[case("12 V / 4 ohm * 1 s", "3 C")]
+    #[case("phys.c * 2 s", "599584916 m")]
+    #[case("10 m / 5 m", "2")]
+    #[case("sin(pi / 2)", "1")]
+    fn evaluate(#[case] expr: &str, #[case] expect: &str) {
+        let mut context = Context::<Quantity>::new();
+        assert_eq!(context.evaluate(expr).unwrap().to_string(), expect);
+    }
+
+    #[rstest]
+    #[case("1 m + 1 s")]
+    #[case("1 m - 1")]
+    #[case("sin(1 m)")]
+    #[case("2 ** (1 s)")]
+    #[case("1 m & 1")]
+    #[case("min(1 m, 1 kg)")]
+    fn dimension_mismatch(#[case] expr: &str) {
+        let mut context = Context::<Quantity>::new();
+        assert!(
+            matches!(
+                context.evaluate(expr),
+                Err(crate::Error::Eval(Error::DimensionMismatch(..)))
+            ),
+            "{expr}"
+        );
+    }
+
+    #[rstest]
+    fn fractional_dimension(#[values("sqrt(2 m)", "(1 m)**1.5", "(1 m)**(1/3)")] expr: &str) {
+        let mut context = Context::<Quantity>::new();
+        assert!(matches!(
+            context.evaluate(expr),
+            Err(crate::Error::Eval(Error::FractionalDimension(_)))
+        ));
+    }
+
+    #[rstest]
+    #[case("60 mph", "km/h", "96.56064")]
+    #[case("100 degF", "degC", "37.77777777777778")]
+    #[case("0 degC", "K", "273.15")]
+    #[case("1 GiB", "MB", "1073.741824")]
+    #[case("3 m**2", "km**2", "0.000003")]
+    #[case("1 J", "W h", "0.0002777777777777778")]
+    #[case("1 km", "m", "1000")]
+    fn convert(#[case] expr: &str, #[case] unit: &str, #[case] expect: &str) {
+        let mut context = Context::<Quantity>::new();
+        let value = context.evaluate(expr).unwrap();
+        let unit = context.evaluate(unit).unwrap();
+        assert_eq!(value.convert(unit).unwrap().to_string(), expect);
+    }
+
+    #[rstest]
+    #[case("20 degC -> degF", "68 degF")]
+    #[case("212 degF in degC", "100 degC")]
+    #[case("36 km/h to m/s", "10 m/s")]
+    #[case("9 kg m/s**2 to N", "9 N")]
+    #[case("1 kWh -> kJ", "3600 kJ")]
+    #[case("1 MiB to (KiB)", "1024 (KiB)")]
+    #[case("100 degF in degC", "37.77777777777778 degC")]
+    #[case("1 m to ft", "3.2808398950131235 ft")]
+    #[case("60 mph to km/h", "96.56064 km/h")]
+    fn conversion_suffix(#[case] expr: &str, #[case] expect: &str) {
+        let mut context = Context::<Quantity>::new();
+        assert_eq!(context.evaluate_annotated(expr).unwrap(), expect);
+    }
+
+    #[test]
+    fn convert_dimension_mismatch() {
+        let mut context = Context::<Quantity>::new();
+        assert!(matches!(
+            context.evaluate_annotated("1 m to s"),
+            Err(crate::Error::Eval(Error::DimensionMismatch(..)))
+        ));
+    }
+
+    #[test]
+    fn conversion_keeps_base_units_in_history() {
+        let mut context = Context::<Quantity>::new();
+        context.evaluate_annotated("1 km to m").unwrap();
+        assert_eq!(context.evaluate("@").unwrap().to_string(), "1000 m");
+    }
+
+    #[rstest]
+    #[case("2026-12-25 - 2026-10-17", "69d")]
+    #[case("2026-10-17T14:30 + 90m 0s", "2026-10-17T16:00:00")]
+    #[case("1 d + 2026-10-17", "2026-10-18")]
+    #[case("2026-03-01 - 1s", "2026-02-28T23:59:59")]
+    #[case("3h 20m * 2", "6h 40m")]
+    #[case("T17:45 - T09:15", "8h 30m")]
+    #[case("2026-10-17 + T14:30", "2026-10-17T14:30:00")]
+    #[case("2026-10-17 + 14:30:05", "2026-10-17T14:30:05")]
+    #[case("unix(1700000000)", "2023-11-14T22:13:20")]
+    #[case("unix(2026-10-17)", "1792195200")]
+    #[case("max(2026-01-01, 2026-03-01)", "2026-03-01")]
+    #[case("2026-01-01 < 2026-03-01", "1")]
+    #[case("1h == 3600 s", "1")]
+    #[case("(2026-12-25 - 2026-10-17) / 1 d", "69")]
+    fn dates(#[case] expr: &str, #[case] expect: &str) {
+        let mut context = Context::<Quantity>::new();
+        assert_eq!(context.evaluate(expr).unwrap().to_string(), expect);
+    }
+
+    #[rstest]
+    fn date_arithmetic(
+        #[values(
+            "2026-10-17 + 2026-10-17",
+            "2 * 2026-10-17",
+            "-2026-10-17",
+            "sqrt(2026-10-17)",
+            "2026-10-17 + 1",
+            "2026-10-17 % 1 d"
+        )]
+        expr: &str,
+    ) {
+        let mut context = Context::<Quantity>::new();
+        assert!(
+            matches!(
+                context.evaluate_annotated(expr),
+                Err(crate::Error::Eval(Error::DateArithmetic))
+            ),
+            "{expr}"
+        );
+    }
+
+    #[test]
+    fn clock() {
+        let mut context = Context::<Quantity>::new();
+        context.clock = Box::new(|| datetime::parse_datetime("2026-10-17T14:30:05.75").unwrap());
+        assert_eq!(
+            context.evaluate("now()").unwrap().to_string(),
+            "2026-10-17T14:30:05"
+        );
+        assert_eq!(
+            context.evaluate("today()").unwrap().to_string(),
+            "2026-10-17"
+        );
+        assert_eq!(
+            context
+                .evaluate("2026-12-25 - today()")
+                .unwrap()
+                .to_string(),
+            "69d"
+        );
+    }
+
+    #[test]
+    fn variables_shadow_units() {
+        let mut context = Context::<Quantity>::new();
+        context.evaluate("m = 5").unwrap();
+        assert_eq!(context.evaluate("3 m").unwrap().to_string(), "15");
+        assert_eq!(context.evaluate("3 km").unwrap().to_string(), "3000 m");
+    }
+}
@@ -0,0 +1,476 @@
+use std::{fmt, str::FromStr};
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One as _, Pow as _};
+
+use super::Quantity;
+
+/// The number of SI base dimensions.
+const BASE_DIMENSIONS: usize = 8;
+
+/// The symbols of the base units, in the order their exponents are stored and displayed.
+///
+/// These are the SI base units, and the byte as the unit of information.
+const BASE_SYMBOLS: [&str; BASE_DIMENSIONS] = ["kg", "m", "s", "A", "K", "mol", "cd", "B"];
+
+/// The physical dimension of a quantity: the exponent of each base unit.
+///
+/// The `Display` impl renders it as a product of units such as `kg m s^-2`, using the symbol of a
+/// coherent derived unit such as `J` when one matches exactly. `FromStr` parses such a product.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Dimension([i8; BASE_DIMENSIONS]);
+
+impl Dimension {
+    const KILOGRAM: Self = Self([1, 0, 0, 0, 0, 0, 0, 0]);
+    const METRE: Self = Self([0, 1, 0, 0, 0, 0, 0, 0]);
+    pub(super) const SECOND: Self = Self([0, 0, 1, 0, 0, 0, 0, 0]);
+    const AMPERE: Self = Self([0, 0, 0, 1, 0, 0, 0, 0]);
+    const KELVIN: Self = Self([0, 0, 0, 0, 1, 0, 0, 0]);
+    const MOLE: Self = Self([0, 0, 0, 0, 0, 1, 0, 0]);
+    const CANDELA: Self = Self([0, 0, 0, 0, 0, 0, 1, 0]);
+    const NEWTON: Self = Self([1, 1, -2, 0, 0, 0, 0, 0]);
+    const JOULE: Self = Self([1, 2, -2, 0, 0, 0, 0, 0]);
+    const WATT: Self = Self([1, 2, -3, 0, 0, 0, 0, 0]);
+    const PASCAL: Self = Self([1, -1, -2, 0, 0, 0, 0, 0]);
+    const HERTZ: Self = Self([0, 0, -1, 0, 0, 0, 0, 0]);
+    const COULOMB: Self = Self([0, 0, 1, 1, 0, 0, 0, 0]);
+    const VOLT: Self = Self([1, 2, -3, -1, 0, 0, 0, 0]);
+    const OHM: Self = Self([1, 2, -3, -2, 0, 0, 0, 0]);
+    const FARAD: Self = Self([-1, -2, 4, 2, 0, 0, 0, 0]);
+    const TESLA: Self = Self([1, 0, -2, -1, 0, 0, 0, 0]);
+    const LITRE: Self = Self([0, 3, 0, 0, 0, 0, 0, 0]);
+    const SPEED: Self = Self([0, 1, -1, 0, 0, 0, 0, 0]);
+    const BYTE: Self = Self([0, 0, 0, 0, 0, 0, 0, 1]);
+
+    /// Whether this is the dimension of a pure number.
+    pub fn is_dimensionless(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The dimension of the product of quantities of these dimensions, or `None` on overflow.
+    pub(crate) fn checked_mul(self, other: Self) -> Option<Self> {
+        self.zip_with(other, i8::checked_add)
+    }
+
+    /// The dimension of the quotient of quantities of these dimensions, or `None` on overflow.
+    pub(crate) fn checked_div(self, other: Self) -> Option<Self> {
+        self.zip_with(other, i8::checked_sub)
+    }
+
+    /// The dimension of a quantity of this dimension raised to the power `numer / denom`.
+    ///
+    /// `None` if an exponent would not be an integer, or would overflow.
+    pub(crate) fn checked_pow(self, numer: i64, denom: i64) -> Option<Self> {
+        let mut out = Self::default();
+        for (out, &exponent) in out.0.iter_mut().zip(&self.0) {
+            let scaled = i64::from(exponent).checked_mul(numer)?;
+            if denom == 0 || scaled % denom != 0 {
+                return None;
+            }
+            *out = i8::try_from(scaled / denom).ok()?;
+        }
+        Some(out)
+    }
+
+    fn zip_with(self, other: Self, f: impl Fn(i8, i8) -> Option<i8>) -> Option<Self> {
+        let mut out = Self::default();
+        for ((out, &left), &right) in out.0.iter_mut().zip(&self.0).zip(&other.0) {
+            *out = f(left, right)?;
+        }
+        Some(out)
+    }
+}
+
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_dimensionless() {
+            return f.write_str("dimensionless");
+        }
+        if let Some(unit) = UNITS
+            .iter()
+            .find(|unit| unit.derived && unit.dimension == *self)
+        {
+            return f.write_str(unit.symbol);
+        }
+        let mut first = true;
+        for (symbol, &exponent) in BASE_SYMBOLS.iter().zip(&self.0) {
+            if exponent == 0 {
+                continue;
+            }
+            if !first {
+                f.write_str(" ")?;
+            }
+            first = false;
+            f.write_str(symbol)?;
+            if exponent != 1 {
+                write!(f, "^{exponent}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Error parsing a [`Dimension`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("\"{0}\" is not a product of units")]
+pub struct ParseDimensionError(String);
+
+impl FromStr for Dimension {
+    type Err = ParseDimensionError;
+
+    /// Parse a product of units such as `m s^-1`, ignoring their scale.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseDimensionError(s.to_owned());
+        s.split_whitespace()
+            .try_fold(Self::default(), |dimension, factor| {
+                let (symbol, exponent) = factor.split_once('^').unwrap_or((factor, "1"));
+                let exponent: i64 = exponent.parse().map_err(|_| err())?;
+                let unit = lookup(symbol).ok_or_else(err)?.dimension;
+                unit.checked_pow(exponent, 1)
+                    .and_then(|unit| dimension.checked_mul(unit))
+                    .ok_or_else(err)
+            })
+    }
+}
+
+/// The size of a unit in terms of the base units: `numer / denom * 10**exponent`.
+#[derive(Clone, Copy)]
+struct Scale {
+    numer: u64,
+    denom: u64,
+    exponent: i32,
+}
+
+impl Scale {
+    const ONE: Self = Self::decimal(1, 0);
+
+    const fn decimal(numer: u64, exponent: i32) -> Self {
+        Self {
+            numer,
+            denom: 1,
+            exponent,
+        }
+    }
+
+    const fn ratio(numer: u64, denom: u64) -> Self {
+        Self {
+            numer,
+            denom,
+            exponent: 0,
+        }
+    }
+
+    /// The exact value of this scale.
+    fn rational(self) -> BigRational {
+        let power = BigRational::from_integer(BigInt::from(10).pow(self.exponent.unsigned_abs()));
+        let power = if self.exponent < 0 {
+            power.recip()
+        } else {
+            power
+        };
+        BigRational::new(self.numer.into(), self.denom.into()) * power
+    }
+}
+
+/// Which prefixes a unit accepts.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Prefixes {
+    None,
+    /// SI prefixes such as the `k` of `km`.
+    Si,
+    /// SI prefixes, and binary prefixes such as the `Ki` of `KiB`.
+    Binary,
+}
+
+/// A named unit of measure.
+struct UnitDef {
+    symbol: &'static str,
+    scale: Scale,
+    /// For a unit of absolute temperature, the amount which is added to a number of these units
+    /// before it is scaled to kelvin: `273.15` for `degC`.
+    offset: Option<Scale>,
+    dimension: Dimension,
+    prefixes: Prefixes,
+    /// Whether this is the coherent SI unit of its dimension, and so names it for display.
+    derived: bool,
+}
+
+impl UnitDef {
+    const fn si(symbol: &'static str, dimension: Dimension, derived: bool) -> Self {
+        Self {
+            symbol,
+            scale: Scale::ONE,
+            offset: None,
+            dimension,
+            prefixes: Prefixes::Si,
+            derived,
+        }
+    }
+
+    const fn scaled(
+        symbol: &'static str,
+        scale: Scale,
+        dimension: Dimension,
+        prefixes: Prefixes,
+    ) -> Self {
+        Self {
+            symbol,
+            scale,
+            offset: None,
+            dimension,
+            prefixes,
+            derived: false,
+        }
+    }
+
+    const fn affine(symbol: &'static str, scale: Scale, offset: Scale) -> Self {
+        Self {
+            symbol,
+            scale,
+            offset: Some(offset),
+            dimension: Dimension::KELVIN,
+            prefixes: Prefixes::None,
+            derived: false,
+        }
+    }
+}
+
+const UNITS: &[UnitDef] = &[
+    UnitDef::scaled(
+        "g",
+        Scale::decimal(1, -3),
+        Dimension::KILOGRAM,
+        Prefixes::Si,
+    ),
+    UnitDef::si("m", Dimension::METRE, false),
+    UnitDef::si("s", Dimension::SECOND, false),
+    UnitDef::si("A", Dimension::AMPERE, false),
+    UnitDef::si("K", Dimension::KELVIN, false),
+    UnitDef::si("mol", Dimension::MOLE, false),
+    UnitDef::si("cd", Dimension::CANDELA, false),
+    UnitDef::si("N", Dimension::NEWTON, true),
+    UnitDef::si("J", Dimension::JOULE, true),
+    UnitDef::si("W", Dimension::WATT, true),
+    UnitDef::si("Pa", Dimension::PASCAL, true),
+    // `s^-1` is more often a rate than a frequency, so it isn't displayed as `Hz`
+    UnitDef::si("Hz", Dimension::HERTZ, false),
+    UnitDef::si("C", Dimension::COULOMB, true),
+    UnitDef::si("V", Dimension::VOLT, true),
+    UnitDef::si("ohm", Dimension::OHM, true),
+    UnitDef::si("F", Dimension::FARAD, true),
+    UnitDef::si("T", Dimension::TESLA, true),
+    UnitDef::scaled("L", Scale::decimal(1, -3), Dimension::LITRE, Prefixes::Si),
+    UnitDef::scaled(
+        "Wh",
+        Scale::decimal(3600, 0),
+        Dimension::JOULE,
+        Prefixes::Si,
+    ),
+    UnitDef::scaled(
+        "eV",
+        Scale::decimal(1_602_176_634, -28),
+        Dimension::JOULE,
+        Prefixes::Si,
+    ),
+    UnitDef::scaled("bar", Scale::decimal(1, 5), Dimension::PASCAL, Prefixes::Si),
+    UnitDef::scaled("B", Scale::ONE, Dimension::BYTE, Prefixes::Binary),
+    UnitDef::scaled("bit", Scale::ratio(1, 8), Dimension::BYTE, Prefixes::Binary),
+    UnitDef::scaled(
+        "minute",
+        Scale::decimal(60, 0),
+        Dimension::SECOND,
+        Prefixes::None,
+    ),
+    UnitDef::scaled(
+        "h",
+        Scale::decimal(3600, 0),
+        Dimension::SECOND,
+        Prefixes::None,
+    ),
+    UnitDef::scaled(
+        "d",
+        Scale::decimal(86400, 0),
+        Dimension::SECOND,
+        Prefixes::None,
+    ),
+    UnitDef::scaled(
+        "ft",
+        Scale::decimal(3048, -4),
+        Dimension::METRE,
+        Prefixes::None,
+    ),
+    UnitDef::scaled(
+        "mi",
+        Scale::decimal(1_609_344, -3),
+        Dimension::METRE,
+        Prefixes::None,
+    ),
+    UnitDef::scaled(
+        "mph",
+        Scale::decimal(44704, -5),
+        Dimension::SPEED,
+        Prefixes::None,
+    ),
+    UnitDef::scaled(
+        "lb",
+        Scale::decimal(45_359_237, -8),
+        Dimension::KILOGRAM,
+        Prefixes::None,
+    ),
+    UnitDef::affine("degC", Scale::ONE, Scale::decimal(27315, -2)),
+    UnitDef::affine("degF", Scale::ratio(5, 9), Scale::decimal(45967, -2)),
+];
+
+/// SI prefixes and the powers of 10 they denote.
+const SI_PREFIXES: &[(&str, i32)] = &[
+    ("Y", 24),
+    ("Z", 21),
+    ("E", 18),
+    ("P", 15),
+    ("T", 12),
+    ("G", 9),
+    ("M", 6),
+    ("k", 3),
+    ("h", 2),
+    ("da", 1),
+    ("d", -1),
+    ("c", -2),
+    ("m", -3),
+    ("u", -6),
+    ("n", -9),
+    ("p", -12),
+    ("f", -15),
+    ("a", -18),
+    ("z", -21),
+    ("y", -24),
+];
+
+/// Binary prefixes and the powers of 1024 they denote.
+const BINARY_PREFIXES: &[(&str, u32)] = &[
+    ("Ki", 1),
+    ("Mi", 2),
+    ("Gi", 3),
+    ("Ti", 4),
+    ("Pi", 5),
+    ("Ei", 6),
+    ("Zi", 7),
+    ("Yi", 8),
+];
+
+/// Find the unit named `name`, which may carry a prefix such as the `k` of `km`.
+///
+/// The result is one such unit, in base units.
+/// Unprefixed units take precedence, so `cd` is a candela rather than a centiday.
+pub(crate) fn lookup(name: &str) -> Option<Quantity> {
+    let (unit, factor) = UNITS
+        .iter()
+        .find(|unit| unit.symbol == name)
+        .map(|unit| (unit, BigRational::one()))
+        .or_else(|| {
+            SI_PREFIXES.iter().find_map(|&(prefix, power)| {
+                let unit = find_prefixed(name, prefix, Prefixes::Si)?;
+                Some((unit, Scale::decimal(1, power).rational()))
+            })
+        })
+        .or_else(|| {
+            BINARY_PREFIXES.iter().find_map(|&(prefix, power)| {
+                let unit = find_prefixed(name, prefix, Prefixes::Binary)?;
+                Some((
+                    unit,
+                    BigRational::from_integer(BigInt::from(1024).pow(power)),
+                ))
+            })
+        })?;
+    let mut quantity = Quantity::new((unit.scale.rational() * factor).into(), unit.dimension);
+    quantity.offset = unit.offset.map(|offset| offset.rational().into());
+    Some(quantity)
+}
+
+/// The unit which `name` names once `prefix` is removed, if it accepts prefixes of that kind.
+fn find_prefixed(name: &str, prefix: &str, kind: Prefixes) -> Option<&'static UnitDef> {
+    let symbol = name.strip_prefix(prefix)?;
+    UNITS.iter().find(|unit| {
+        unit.symbol == symbol
+            && match kind {
+                Prefixes::None => false,
+                Prefixes::Si => unit.prefixes != Prefixes::None,
+                Prefixes::Binary => unit.prefixes == Prefixes::Binary,
+            }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("m", 1, 1, Dimension::METRE)]
+    #[case("km", 1000, 1, Dimension::METRE)]
+    #[case("kg", 1, 1, Dimension::KILOGRAM)]
+    #[case("mg", 1, 1_000_000, Dimension::KILOGRAM)]
+    #[case("cd", 1, 1, Dimension::CANDELA)]
+    #[case("dam", 10, 1, Dimension::METRE)]
+    #[case("ms", 1, 1000, Dimension::SECOND)]
+    #[case("kWh", 3_600_000, 1, Dimension::JOULE)]
+    #[case("mL", 1, 1_000_000, Dimension::LITRE)]
+    #[case("ft", 381, 1250, Dimension::METRE)]
+    #[case("mph", 2794, 6250, Dimension::SPEED)]
+    #[case("MB", 1_000_000, 1, Dimension::BYTE)]
+    #[case("GiB", 1 << 30, 1, Dimension::BYTE)]
+    #[case("Kibit", 128, 1, Dimension::BYTE)]
+    #[case("degF", 5, 9, Dimension::KELVIN)]
+    fn lookup_units(
+        #[case] name: &str,
+        #[case] numer: u64,
+        #[case] denom: u64,
+        #[case] dimension: Dimension,
+    ) {
+        let unit = lookup(name).unwrap();
+        assert_eq!(
+            unit.value,
+            BigRational::new(numer.into(), denom.into()).into()
+        );
+        assert_eq!(unit.dimension, dimension);
+    }
+
+    #[rstest]
+    fn lookup_non_units(
+        #[values("x", "kh", "kft", "min", "k", "KiJ", "kdegC", "iB", "")] name: &str,
+    ) {
+        assert_eq!(lookup(name), None);
+    }
+
+    #[rstest]
+    #[case(Dimension::METRE, "m")]
+    #[case(Dimension::JOULE, "J")]
+    #[case(Dimension::HERTZ, "s^-1")]
+    #[case(Dimension([-1, 3, -2, 0, 0, 0, 0, 0]), "kg^-1 m^3 s^-2")]
+    #[case(Dimension([0, 0, -1, 0, 0, 0, 0, 1]), "s^-1 B")]
+    #[case(Dimension::default(), "dimensionless")]
+    fn display(#[case] dimension: Dimension, #[case] expect: &str) {
+        assert_eq!(dimension.to_string(), expect);
+    }
+
+    #[rstest]
+    #[case("m s^-1", Dimension::SPEED)]
+    #[case("J s", Dimension([1, 2, -1, 0, 0, 0, 0, 0]))]
+    #[case("W m^-2 K^-4", Dimension([1, 0, -3, 0, -4, 0, 0, 0]))]
+    #[case("kg m^2 s^-2", Dimension::JOULE)]
+    fn parse(#[case] input: &str, #[case] expect: Dimension) {
+        assert_eq!(input.parse(), Ok(expect));
+    }
+
+    #[test]
+    fn pow() {
+        let area = Dimension([0, 2, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(area.checked_pow(1, 2), Some(Dimension::METRE));
+        assert_eq!(Dimension::METRE.checked_pow(1, 2), None);
+        assert_eq!(
+            Dimension::METRE.checked_pow(-3, 1),
+            Some(Dimension([0, -3, 0, 0, 0, 0, 0, 0]))
+        );
+        assert_eq!(Dimension::METRE.checked_pow(200, 1), None);
+    }
+}
@@ -0,0 +1,148 @@
+use std::{cmp::Ordering, fmt};
+
+use calc::{
+    ast::{Constant, Function, InfixOperator, PrefixOperator},
+    types::Calcable,
+    Backend, Context, Error, ValueError,
+};
+use num_runtime_fmt::NumFmt;
+
+/// A fixed-point number with two decimal places, stored as hundredths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Fixed(i64);
+
+impl From<bool> for Fixed {
+    fn from(b: bool) -> Self {
+        Fixed(i64::from(b) * 100)
+    }
+}
+
+impl fmt::Display for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        write!(f, "{sign}{}.{:02}", self.0.abs() / 100, self.0.abs() % 100)
+    }
+}
+
+impl Calcable for Fixed {
+    fn parse(literal: &str, radix: u32, _backend: Backend) -> Result<Self, ValueError> {
+        let unsupported = || ValueError::Unsupported(format!("literal {literal}"));
+        if radix != 10 {
+            return Err(unsupported());
+        }
+        let (whole, frac) = literal.split_once('.').unwrap_or((literal, ""));
+        if frac.len() > 2 {
+            return Err(unsupported());
+        }
+        let whole: i64 = whole.parse().map_err(|_| unsupported())?;
+        let frac: i64 = format!("{frac:0<2}").parse().map_err(|_| unsupported())?;
+        Ok(Fixed(whole * 100 + frac))
+    }
+
+    fn constant(constant: Constant) -> Result<Self, ValueError> {
+        Err(ValueError::Unsupported(format!("{constant:?}")))
+    }
+
+    fn prefix(
+        operator: PrefixOperator,
+        operand: Self,
+        _backend: Backend,
+    ) -> Result<Self, ValueError> {
+        match operator {
+            PrefixOperator::Negation => Ok(Fixed(-operand.0)),
+            PrefixOperator::Not => Err(ValueError::Unsupported("!".into())),
+        }
+    }
+
+    fn infix(
+        operator: InfixOperator,
+        left: Self,
+        right: Self,
+        _backend: Backend,
+    ) -> Result<Self, ValueError> {
+        match operator {
+            InfixOperator::Add => Ok(Fixed(left.0 + right.0)),
+            InfixOperator::Sub => Ok(Fixed(left.0 - right.0)),
+            InfixOperator::Mul => Ok(Fixed(left.0 * right.0 / 100)),
+            InfixOperator::Div => left
+                .0
+                .checked_mul(100)
+                .and_then(|n| n.checked_div(right.0))
+                .map(Fixed)
+                .ok_or(calc::ArithmeticError::DivideBy0.into()),
+            _ => Err(ValueError::Unsupported(format!("{operator:?}"))),
+        }
+    }
+
+    fn function(
+        function: Function,
+        args: Vec<Self>,
+        _backend: Backend,
+    ) -> Result<Self, ValueError> {
+        match function {
+            Function::Abs => Ok(Fixed(args[0].0.abs())),
+            Function::Max => Ok(args.into_iter().max().expect("arity has been checked")),
+            _ => Err(ValueError::Unsupported(format!("{function:?}"))),
+        }
+    }
+
+    fn numeric_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+
+    fn is_truthy(&self) -> bool {
+        self.0 != 0
+    }
+
+    fn format(&self, _format: &NumFmt) -> Result<String, num_runtime_fmt::Error> {
+        Ok(self.to_string())
+    }
+}
+
+#[test]
+fn fixed_point_arithmetic() {
+    let mut context = Context::<Fixed>::new();
+    assert_eq!(context.evaluate("19.99 * 3").unwrap(), Fixed(5997));
+    assert_eq!(context.evaluate("price = @ / 4").unwrap(), Fixed(1499));
+    assert_eq!(context.evaluate("abs(-price) > 10").unwrap(), Fixed(100));
+    assert_eq!(
+        context
+            .evaluate_annotated("max(1, price, 2.5) :.2")
+            .unwrap(),
+        "14.99"
+    );
+}
+
+#[test]
+fn fixed_point_user_functions() {
+    let mut context = Context::<Fixed>::new();
+    context.define("tax(x) = x * 0.2").unwrap();
+    assert_eq!(context.evaluate("tax(50)").unwrap(), Fixed(1000));
+}
+
+#[test]
+fn fixed_point_unsupported_operations() {
+    let mut context = Context::<Fixed>::new();
+    for expr in ["2 ** 3", "sqrt(4)", "0xff", "1.005"] {
+        assert!(
+            matches!(
+                context.evaluate(expr),
+                Err(Error::Eval(ValueError::Unsupported(_)))
+            ),
+            "{expr}"
+        );
+    }
+    assert!(context.history.is_empty());
+}
+
+#[test]
+fn fixed_point_constants() {
+    let mut context = Context::<Fixed>::new();
+    assert!(context.constants.is_empty());
+    assert!(matches!(
+        context.evaluate("pi"),
+        Err(Error::Eval(ValueError::UnknownVariable(_)))
+    ));
+    context.define_constant("pi", Fixed(314)).unwrap();
+    assert_eq!(context.evaluate("2pi").unwrap(), Fixed(628));
+}
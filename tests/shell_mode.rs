@@ -1,6 +1,15 @@
-use calc::Context;
+use std::time::{Duration, UNIX_EPOCH};
+
+use calc::{
+    ast::{Arity, ParseError as UserParseError},
+    types::Calcable,
+    ArithmeticError, Backend, Context, Error, MachineType, Overflow, Precision, Quantity, Value,
+    ValueError,
+};
+use lalrpop_util::ParseError;
 use lazy_static::lazy_static;
 use regex::Regex;
+use rstest::rstest;
 
 lazy_static! {
     static ref INPUT_RE: Regex = Regex::new(r"^\s*\[\d+\]: (.*)$").unwrap();
@@ -40,7 +49,10 @@ fn parse_expressions(input: &str) -> Vec<ShellCase> {
 }
 
 fn assert_expressions(expressions: &[ShellCase]) {
-    let mut context = Context::default();
+    assert_expressions_in(&mut Context::default(), expressions);
+}
+
+fn assert_expressions_in<N: Calcable>(context: &mut Context<N>, expressions: &[ShellCase]) {
     for ShellCase { input, expect } in expressions {
         let result = context.evaluate(input).unwrap();
         assert_eq!(&result.to_string(), expect);
@@ -61,7 +73,7 @@ fn readme_2_shell_mode() {
     [4]: @@@*2
     -30
     [5]: ln(-1)
-    NaN
+    3.141592653589793i
     "#;
 
     let expressions = parse_expressions(CASE);
@@ -74,7 +86,7 @@ fn issue_14_example_1() {
     [1]: 528500/100
     5285
     [2]: @/2
-    2642.5
+    5285/2
     [3]: @@//2
     2642
     "#;
@@ -99,3 +111,1146 @@ fn issue_14_example_2() {
     let expressions = parse_expressions(CASE);
     assert_expressions(&expressions);
 }
+
+#[test]
+fn readme_3_variables() {
+    const CASE: &str = r#"
+    [0]: r = 3
+    3
+    [1]: let area = pi * r**2
+    28.274333882308138
+    [2]: area / r
+    9.42477796076938
+    [3]: x = @ // 1
+    9
+    [4]: x x x
+    81
+    "#;
+
+    let expressions = parse_expressions(CASE);
+    assert_expressions(&expressions);
+}
+
+#[test]
+fn unknown_variable_is_an_eval_error() {
+    let mut context = Context::default();
+    let result = context.evaluate("nope + 1");
+    assert!(matches!(
+        result,
+        Err(Error::Eval(ValueError::UnknownVariable(name))) if name == "nope"
+    ));
+    assert!(context.history.is_empty());
+}
+
+#[test]
+fn user_defined_functions() {
+    let mut context = Context::default();
+    assert_eq!(
+        context.evaluate_annotated("f(x, y) = x**2 + y").unwrap(),
+        "f(x, y) = x**2 + y"
+    );
+    context
+        .define("cache(lines, ways) = lines * ways * 64")
+        .unwrap();
+    // parameters shadow variables of the same name
+    context.evaluate("x = 100").unwrap();
+
+    assert_eq!(context.evaluate("f(3, 1)").unwrap().to_string(), "10");
+    assert_eq!(context.evaluate("f(x, 1)").unwrap().to_string(), "10001");
+    assert_eq!(
+        context.evaluate_annotated("cache(512, 8)").unwrap(),
+        "262144"
+    );
+    // nothing but the evaluated expressions are recorded in the history
+    assert_eq!(context.history.len(), 4);
+}
+
+#[test]
+fn user_defined_function_errors() {
+    let mut context = Context::default();
+    context.define("f(x) = x + 1").unwrap();
+    context.define("loop(x) = loop(x)").unwrap();
+
+    assert!(matches!(
+        context.evaluate("f(1, 2)"),
+        Err(Error::Eval(ValueError::Arity(name, arity, 2))) if name == "f" && arity.min == 1
+    ));
+    assert!(matches!(
+        context.evaluate("g(1)"),
+        Err(Error::Eval(ValueError::UnknownFunction(name))) if name == "g"
+    ));
+    assert!(matches!(
+        context.evaluate("loop(1)"),
+        Err(Error::Eval(ValueError::RecursionLimit(_)))
+    ));
+    assert!(matches!(
+        context.define("h(a, a) = a"),
+        Err(Error::Parse(_))
+    ));
+}
+
+#[test]
+fn native_functions() {
+    let mut context = Context::default();
+    context
+        .register_fn("pages", 1, |args| Ok((args[0].clone() / 4096_u64).ceil()))
+        .unwrap();
+    context
+        .register_fn("total", Arity::at_least(1), |args| {
+            Ok(args.into_iter().fold(Value::from(0_u64), |sum, n| sum + n))
+        })
+        .unwrap();
+    context
+        .register_fn("fail", 0, |_| Err(ArithmeticError::DivideBy0.into()))
+        .unwrap();
+
+    assert_eq!(context.evaluate("pages(10000)").unwrap().to_string(), "3");
+    assert_eq!(
+        context
+            .evaluate("total(1, 2, pages(1))")
+            .unwrap()
+            .to_string(),
+        "4"
+    );
+    assert!(matches!(
+        context.evaluate("pages(1, 2)"),
+        Err(Error::Eval(ValueError::Arity(name, _, 2))) if name == "pages"
+    ));
+    assert!(matches!(
+        context.evaluate("fail()"),
+        Err(Error::Eval(ValueError::Arithmetic(
+            ArithmeticError::DivideBy0
+        )))
+    ));
+
+    // native and user-defined functions replace one another
+    context.define("pages(x) = 0").unwrap();
+    assert_eq!(context.evaluate("pages(10000)").unwrap().to_string(), "0");
+    context
+        .register_fn("pages", 1, |args| Ok(args[0].clone()))
+        .unwrap();
+    assert_eq!(context.evaluate("pages(7)").unwrap().to_string(), "7");
+}
+
+#[rstest]
+fn native_function_reserved_names(#[values("sin", "if", "2x", "a b", "")] name: &str) {
+    let mut context = Context::default();
+    assert!(matches!(
+        context.register_fn(name, 1, |args| Ok(args[0].clone())),
+        Err(Error::InvalidName(_))
+    ));
+}
+
+#[rstest]
+fn native_function_constant_names(#[values("pi", "e", "tau")] name: &str) {
+    let mut context = Context::default();
+    assert!(matches!(
+        context.register_fn(name, 1, |args| Ok(args[0].clone())),
+        Err(Error::NameConflict(_, "constant"))
+    ));
+}
+
+#[test]
+fn builtin_constants() {
+    const CASE: &str = r#"
+    [0]: tau == 2pi
+    1
+    [1]: phi**2 - phi
+    1
+    [2]: sqrt2**2 - 2 < 1e-15
+    1
+    [3]: exp(ln2)
+    2
+    [4]: -inf < 0 && 0 < inf
+    1
+    [5]: nan == nan
+    0
+    [6]: e(2) == 2e
+    1
+    "#;
+
+    let expressions = parse_expressions(CASE);
+    assert_expressions(&expressions);
+}
+
+#[rstest]
+fn non_finite_constants_format(
+    #[values(Backend::Float, Backend::StrictFloat, Backend::Decimal { scale: 28 })]
+    backend: Backend,
+) {
+    let mut context = Context::<Quantity> {
+        backend,
+        ..Context::new()
+    };
+    for (expr, expect) in [
+        ("inf", "inf"),
+        ("-inf", "-inf"),
+        ("nan", "NaN"),
+        ("round(nan)", "NaN"),
+        ("inf m", "inf m"),
+    ] {
+        assert_eq!(context.evaluate_annotated(expr).unwrap(), expect, "{expr}");
+    }
+}
+
+#[test]
+fn physical_constants() {
+    const CASE: &str = r#"
+    [0]: phys.c
+    299792458
+    [1]: c = 3
+    3
+    [2]: c phys.c
+    899377374
+    [3]: phys.G == 6.6743e-11
+    1
+    [4]: phys.N_A phys.e_charge > 96485
+    1
+    "#;
+
+    let expressions = parse_expressions(CASE);
+    assert_expressions(&expressions);
+}
+
+#[test]
+fn readme_units() {
+    const CASE: &str = r#"
+    [0]: 3 m / 2 s
+    3/2 m s^-1
+    [1]: 5 kW * 3 h
+    54000000 J
+    [2]: 2 km + 300 m
+    2300 m
+    "#;
+
+    let expressions = parse_expressions(CASE);
+    let mut context = Context::<Quantity>::new();
+    assert_expressions_in(&mut context, &expressions);
+    assert!(matches!(
+        context.evaluate("1 m + 1 s"),
+        Err(Error::Eval(ValueError::DimensionMismatch(..)))
+    ));
+}
+
+#[test]
+fn unit_conversions() {
+    let mut context = Context::<Quantity>::new();
+    for (expr, expect) in [
+        ("212 degF in degC", "100 degC"),
+        ("1 GiB -> MiB", "1024 MiB"),
+        ("36 km/h to m/s", "10 m/s"),
+    ] {
+        assert_eq!(context.evaluate_annotated(expr).unwrap(), expect);
+    }
+    assert!(matches!(
+        context.evaluate_annotated("1 m to s"),
+        Err(Error::Eval(ValueError::DimensionMismatch(..)))
+    ));
+}
+
+#[test]
+fn dates_and_durations() {
+    let mut context = Context::<Quantity>::new();
+    context.clock = Box::new(|| UNIX_EPOCH + Duration::from_secs(1_792_247_400));
+    for (expr, expect) in [
+        ("now()", "2026-10-17T14:30:00"),
+        ("2026-12-25 - today()", "69d"),
+        ("today() + T17:45", "2026-10-17T17:45:00"),
+        ("deadline = now() + 3h 20m", "2026-10-17T17:50:00"),
+        ("deadline - now() to s", "12000 s"),
+        ("3h 20m to minute", "200 minute"),
+    ] {
+        assert_eq!(context.evaluate_annotated(expr).unwrap(), expect);
+    }
+}
+
+#[test]
+fn dates_out_of_range() {
+    let mut context = Context::<Quantity>::new();
+    for expr in [
+        "unix(86400 * 9223372036854775800)",
+        "unix(1e30)",
+        "unix(nan)",
+        "unix(inf)",
+        "unix(0) + 1e30 s",
+        "2026-10-17 - 1e30 s",
+    ] {
+        assert!(
+            matches!(
+                context.evaluate_annotated(expr),
+                Err(Error::Eval(ValueError::InvalidDate(_)))
+            ),
+            "{expr}"
+        );
+    }
+}
+
+#[test]
+fn dates_need_quantities() {
+    let mut context = Context::default();
+    for expr in ["2026-10-17T14:30", "3h 20m", "T14:30", "now()", "unix(0)"] {
+        assert!(
+            matches!(
+                context.evaluate(expr),
+                Err(Error::Eval(ValueError::Unsupported(_)))
+            ),
+            "{expr}"
+        );
+    }
+}
+
+#[test]
+fn date_and_duration_literals_give_way() {
+    // a date is a subtraction without dates, and a time of day without `T` or seconds is a format
+    let mut context = Context::default();
+    assert_eq!(context.evaluate("2000-10-10").unwrap(), Value::from(1980));
+    assert_eq!(context.evaluate_annotated("42:08").unwrap(), "00000042");
+
+    let mut context = Context::<Quantity>::new();
+    assert_eq!(context.evaluate_annotated("42:08").unwrap(), "00000042");
+    // a duration's unit which names a variable multiplies it, and minutes alone are metres
+    for (expr, expect) in [
+        ("3m", "3 m"),
+        ("3h", "3h"),
+        ("m = 2", "2"),
+        ("3m", "6"),
+        ("3m 5s", "3m 5s"),
+        ("h = 5", "5"),
+        ("1.5h", "7.5"),
+    ] {
+        assert_eq!(context.evaluate_annotated(expr).unwrap(), expect, "{expr}");
+    }
+    // so does a parameter
+    context.define("f(s) = 2s").unwrap();
+    assert_eq!(context.evaluate_annotated("f(4)").unwrap(), "8");
+}
+
+#[test]
+fn custom_constants() {
+    let mut context = Context::default();
+    context
+        .define_constant("c", Value::from(299_792_458_u64))
+        .unwrap();
+    context
+        .load_constants("# astronomy\n\nau = 149597870700\nlightsecond = c\n")
+        .unwrap();
+    assert_eq!(
+        context.evaluate("au / lightsecond").unwrap().to_string(),
+        "1024642950/2053373"
+    );
+
+    // constants can't be reassigned, or shadow functions and variables
+    assert!(matches!(
+        context.evaluate("c = 3"),
+        Err(Error::NameConflict(name, "constant")) if name == "c"
+    ));
+    assert!(matches!(
+        context.define("pi(x) = x"),
+        Err(Error::NameConflict(name, "constant")) if name == "pi"
+    ));
+    context.define("f(x) = x").unwrap();
+    context.evaluate("v = 1").unwrap();
+    assert!(matches!(
+        context.define_constant("f", Value::from(1_u64)),
+        Err(Error::NameConflict(_, "function"))
+    ));
+    assert!(matches!(
+        context.load_constants("v = 2"),
+        Err(Error::NameConflict(_, "variable"))
+    ));
+    assert!(matches!(
+        context.load_constants("sin = 2"),
+        Err(Error::InvalidName(_))
+    ));
+    assert!(matches!(
+        context.load_constants("# lengths\nmile = 1609.344\n\nfurlong 201.168\n"),
+        Err(Error::ConstantSyntax(4))
+    ));
+    // failed assignments aren't recorded in the history
+    assert_eq!(context.history.len(), 2);
+
+    // constants may be namespaced like the built-in physical constants
+    context.load_constants("units.ft = 12").unwrap();
+    assert_eq!(context.evaluate("6units.ft").unwrap().to_string(), "72");
+
+    // parameters may shadow constants, though
+    context.define("g(c) = c + 1").unwrap();
+    assert_eq!(context.evaluate("g(1)").unwrap().to_string(), "2");
+}
+
+#[test]
+fn multi_argument_functions() {
+    const CASE: &str = r#"
+    [0]: deg(atan2(1, -1))
+    135
+    [1]: hypot(3, 4)
+    5
+    [2]: min(3, -5/2, 7)
+    -5/2
+    [3]: max(3, -5/2, 7)
+    7
+    [4]: log(8, 2)
+    3
+    [5]: log(100)
+    2
+    [6]: clamp(12, 0, 10)
+    10
+    [7]: clamp(-3/2, 0, 10)
+    0
+    [8]: log(1000, 10)
+    3
+    [9]: log(8, 1)
+    NaN
+    "#;
+
+    let expressions = parse_expressions(CASE);
+    assert_expressions(&expressions);
+}
+
+#[test]
+fn builtin_function_arity_is_checked() {
+    let mut context = Context::default();
+    assert!(matches!(
+        context.evaluate("atan2(1)"),
+        Err(Error::Eval(ValueError::Arity(name, _, 1))) if name == "atan2"
+    ));
+    assert!(matches!(
+        context.evaluate("min()"),
+        Err(Error::Eval(ValueError::Arity(name, _, 0))) if name == "min"
+    ));
+    assert_eq!(
+        context.evaluate("sqrt(1, 2)").unwrap_err().to_string(),
+        "Evaluating"
+    );
+}
+
+#[test]
+fn comparisons_and_conditionals() {
+    const CASE: &str = r#"
+    [0]: 1 + 1 == 2
+    1
+    [1]: 3 < 2
+    0
+    [2]: 2 <= 2 && 3 != 4
+    1
+    [3]: 0 || 1 > 2
+    0
+    [4]: (2 > 1) + (3 ≥ 1)
+    2
+    [5]: if 2 >= 1 then 10 else 20
+    10
+    [6]: if 0 then 10 else 20 + 1
+    21
+    "#;
+
+    let expressions = parse_expressions(CASE);
+    assert_expressions(&expressions);
+}
+
+#[test]
+fn conditionals_short_circuit() {
+    let mut context = Context::default();
+    context
+        .define("fact(n) = if n <= 1 then 1 else n * fact(n - 1)")
+        .unwrap();
+    assert_eq!(context.evaluate("fact(10)").unwrap().to_string(), "3628800");
+    // the unknown name on the right is never evaluated
+    assert_eq!(context.evaluate("0 && nope").unwrap().to_string(), "0");
+    assert_eq!(context.evaluate("1 || nope").unwrap().to_string(), "1");
+}
+
+#[test]
+fn fractional_and_scientific_literals() {
+    const CASE: &str = r#"
+    [0]: 1.5 + .25
+    1.75
+    [1]: 6.022e23 > 6e23
+    1
+    [2]: 1e3 + 1
+    1001
+    [3]: 1E-9 * 1e9
+    1
+    [4]: 1_000.000_1 - 1_000
+    0.00009999999997489795
+    "#;
+
+    let expressions = parse_expressions(CASE);
+    assert_expressions(&expressions);
+}
+
+#[test]
+fn implicit_multiplication() {
+    const CASE: &str = r#"
+    [0]: 2pi
+    6.283185307179586
+    [1]: 3(4+5)
+    27
+    [2]: a = 5
+    5
+    [3]: b = 3
+    3
+    [4]: (a+b)(a-b)
+    16
+    [5]: 2 sqrt(4)
+    4
+    [6]: 1/2a
+    1/10
+    [7]: a(b + 1)
+    20
+    [8]: 2 -1
+    1
+    [9]: x = 2
+    2
+    [10]: 3 x x
+    6
+    "#;
+
+    let expressions = parse_expressions(CASE);
+    assert_expressions(&expressions);
+}
+
+#[test]
+fn implicit_multiplication_excludes_literals() {
+    let mut context = Context::default();
+    assert!(matches!(context.evaluate("1 000"), Err(Error::Parse(_))));
+}
+
+#[test]
+fn implicit_multiplication_is_a_normal_multiply() {
+    use calc::ast::{parser::ExprParser, Expr, InfixOperator};
+
+    let expr = ExprParser::new().parse("2pi").unwrap();
+    assert!(matches!(expr, Expr::Infix(_, InfixOperator::Mul, _)));
+}
+
+#[test]
+fn exponent_precedence() {
+    const CASE: &str = r#"
+    [0]: 2**3**2
+    512
+    [1]: -2**2
+    -4
+    [2]: (-2)**2
+    4
+    [3]: 2**-1
+    1/2
+    [4]: 2pi**2 == 2*(pi**2)
+    1
+    [5]: 1 << 2**2
+    16
+    [6]: a = 3
+    3
+    [7]: -a**2
+    -9
+    [8]: (1/3)**-1
+    3
+    [9]: (2/3)**-2
+    9/4
+    "#;
+
+    let expressions = parse_expressions(CASE);
+    assert_expressions(&expressions);
+}
+
+#[test]
+fn arbitrary_precision_integers() {
+    const CASE: &str = r#"
+    [0]: 2**200
+    1606938044258990275541962092341162602522202993782792835301376
+    [1]: 0xffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff * 0xffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff
+    115792089237316195423570985008687907852589419931798687112530834793049593217025
+    [2]: @[0] - 2**200 + 5
+    5
+    [3]: -170141183460469231731687303715884105728 - 1
+    -170141183460469231731687303715884105729
+    [4]: 340282366920938463463374607431768211456 > 2**127
+    1
+    "#;
+
+    let expressions = parse_expressions(CASE);
+    assert_expressions(&expressions);
+}
+
+#[test]
+fn arbitrary_precision_factorial() {
+    let mut context = Context::default();
+    context
+        .define("fact(n) = if n <= 1 then 1 else n * fact(n - 1)")
+        .unwrap();
+    let result = context.evaluate("fact(60)").unwrap();
+    assert_eq!(
+        result.to_string(),
+        "8320987112741390144276341183223364380754172606361245952449277696409600000000000000"
+    );
+}
+
+#[test]
+fn factorial() {
+    const CASE: &str = r#"
+    [0]: 100!
+    93326215443944152681699238856266700490715968264381621468592963895217599993229915608941463976156518286253697920827223758251185210916864000000000000000000000000
+    [1]: @ == factorial(100)
+    1
+    [2]: 2**3!
+    64
+    [3]: (1 + 2)! + 1
+    7
+    [4]: n = 4
+    4
+    [5]: 2n!
+    48
+    [6]: 5! == 120
+    1
+    "#;
+
+    let expressions = parse_expressions(CASE);
+    assert_expressions(&expressions);
+
+    let mut context = Context::<Value>::default();
+    assert!(matches!(
+        context.evaluate("(-1)!"),
+        Err(Error::Eval(ValueError::Domain(_)))
+    ));
+    for expr in ["100000!", "factorial(2**40)"] {
+        assert!(
+            matches!(
+                context.evaluate(expr),
+                Err(Error::Eval(ValueError::Arithmetic(
+                    ArithmeticError::Overflow
+                )))
+            ),
+            "{expr}"
+        );
+    }
+}
+
+#[test]
+fn exact_rational_division() {
+    const CASE: &str = r#"
+    [0]: 1/3 + 1/6
+    1/2
+    [1]: @ * 4
+    2
+    [2]: 2/3 - 1
+    -1/3
+    [3]: (2/3)**2
+    4/9
+    [4]: 7/2 // 1
+    3
+    [5]: round(7/2)
+    4
+    [6]: 1/3 == 2/6
+    1
+    [7]: 1/3 + 0.5
+    0.8333333333333333
+    [8]: 1/0.0
+    inf
+    "#;
+
+    let expressions = parse_expressions(CASE);
+    assert_expressions(&expressions);
+}
+
+#[test]
+fn division_by_zero() {
+    let mut context = Context::<Value>::default();
+    for expr in [
+        "1/0",
+        "7 // 0",
+        "-9223372036854775808 % 0",
+        "0**-1",
+        "1.5 / 0",
+        "(1/3) % 0",
+    ] {
+        assert!(
+            matches!(
+                context.evaluate(expr),
+                Err(Error::Eval(ValueError::Arithmetic(
+                    ArithmeticError::DivideBy0
+                )))
+            ),
+            "{expr}"
+        );
+    }
+    assert_eq!(
+        context.evaluate("-9223372036854775808 % -1").unwrap(),
+        Value::from(0_u64)
+    );
+    assert!(matches!(context.evaluate("1 % 0.0").unwrap(), Value::Float(n) if n.is_nan()));
+}
+
+#[test]
+fn non_finite_results_format() {
+    let mut context = Context::<Quantity>::new();
+    for (expr, expect) in [
+        ("1/0.0", "inf"),
+        ("-1/0.0", "-inf"),
+        ("0.1 // 0.0", "inf"),
+        ("1 % 0.0", "NaN"),
+        ("2**262144", "inf"),
+        ("1e99999999999", "inf"),
+        ("1e-99999999999", "0"),
+        ("1/0.0 :x", "inf"),
+        ("(1/0.0) * i", "NaN+infi"),
+        ("abs(-9223372036854775807 - 1)", "9223372036854775808"),
+        (
+            "abs(-170141183460469231731687303715884105727 - 1)",
+            "170141183460469231731687303715884105728",
+        ),
+    ] {
+        assert_eq!(context.evaluate_annotated(expr).unwrap(), expect, "{expr}");
+    }
+}
+
+#[test]
+fn strict_float() {
+    let mut context = Context::<Value> {
+        backend: Backend::StrictFloat,
+        ..Default::default()
+    };
+    for expr in [
+        "sqrt(-1)",
+        "ln(0)",
+        "asin(2)",
+        "log(8, 1)",
+        "log(8, 0)",
+        "log(8, -2)",
+        "(-8)**0.5",
+    ] {
+        assert!(
+            matches!(
+                context.evaluate(expr),
+                Err(Error::Eval(ValueError::Domain(_)))
+            ),
+            "{expr}"
+        );
+    }
+    assert!(matches!(
+        context.evaluate("(-8)**0.5"),
+        Err(Error::Eval(ValueError::Domain(name))) if name == "**"
+    ));
+    assert!(matches!(
+        context.evaluate("asin(2)"),
+        Err(Error::Eval(ValueError::Domain(name))) if name == "asin"
+    ));
+    for (expr, error) in [
+        ("1 / 0.0", ArithmeticError::DivideBy0),
+        ("0.0 ** -1", ArithmeticError::DivideBy0),
+        ("1e308 * 10", ArithmeticError::Overflow),
+        ("exp(1000)", ArithmeticError::Overflow),
+    ] {
+        let result = context.evaluate(expr);
+        assert!(
+            matches!(result, Err(Error::Eval(ValueError::Arithmetic(ref e))) if std::mem::discriminant(e) == std::mem::discriminant(&error)),
+            "{expr}: {result:?}"
+        );
+    }
+    // complex and non-finite operands are not checked
+    assert_eq!(context.evaluate("i**2").unwrap(), Value::from(-1));
+    assert!(context.evaluate("sqrt(-4i)").is_ok());
+    assert!(matches!(context.evaluate("inf - 1").unwrap(), Value::Float(n) if n.is_infinite()));
+    assert_eq!(context.evaluate("sqrt(2.25)").unwrap(), Value::Float(1.5));
+}
+
+#[rstest]
+#[case("2**53 + 1 + 0.5", true)]
+#[case("(2**64 + 1) * 1.0", true)]
+#[case("sqrt(2**60 + 1)", true)]
+#[case("(1/3) * 1.0", true)]
+#[case("(2**128 + 1) as f64", true)]
+#[case("2**53 * 1.0", false)]
+#[case("(1/4) * 1.0", false)]
+#[case("sqrt(2**60)", false)]
+#[case("18446744073709551615 + 1", false)]
+#[case("0.1 + 0.2", false)]
+fn precision_policy(#[case] expr: &str, #[case] inexact: bool) {
+    let mut context = Context::<Value> {
+        precision: Precision::Warn,
+        ..Default::default()
+    };
+    context.evaluate(expr).unwrap();
+    assert_eq!(context.inexact.get(), inexact);
+
+    context.precision = Precision::Error;
+    let result = context.evaluate(expr);
+    assert_eq!(
+        matches!(result, Err(Error::Eval(ValueError::Inexact))),
+        inexact,
+        "{result:?}"
+    );
+
+    // not tracked by default
+    context.precision = Precision::Allow;
+    context.evaluate(expr).unwrap();
+    assert!(!context.inexact.get());
+}
+
+#[test]
+fn precision_policy_in_quantities() {
+    let mut context = Context::<Quantity>::new();
+    context.precision = Precision::Warn;
+    context.evaluate("(2**60 + 1) m * 1.5").unwrap();
+    assert!(context.inexact.get());
+    context.evaluate("2 km + 300 m").unwrap();
+    assert!(!context.inexact.get());
+}
+
+#[test]
+fn decimal_backend() {
+    const CASE: &str = r#"
+    [0]: 0.1 + 0.2 == 0.3
+    1
+    [1]: 0.1 + 0.2
+    0.3
+    [2]: 19.99 * 3
+    59.97
+    [3]: 10.00 / 3
+    3.33
+    [4]: 1.005 + 0
+    1.01
+    [5]: 0.1 + 1/3
+    0.43
+    [6]: round(2.5)
+    3
+    [7]: floor(-2.5)
+    -3
+    [8]: ceil(2.01)
+    3
+    [9]: 10.50 // 0.25
+    42
+    [10]: 1/3
+    0.33
+    [11]: 10/3
+    3.33
+    [12]: round(2.675*100)/100
+    2.68
+    [13]: 10/4
+    2.50
+    "#;
+
+    let mut context = Context {
+        backend: Backend::Decimal { scale: 2 },
+        ..Default::default()
+    };
+    let expressions = parse_expressions(CASE);
+    assert_expressions_in(&mut context, &expressions);
+}
+
+#[test]
+fn machine_types() {
+    const CASE: &str = r#"
+    [0]: !0x0f
+    240
+    [1]: 200 + 100
+    44
+    [2]: 0xf0 >> 4 | 0x0f << 4
+    255
+    [3]: 0x81 <<< 1
+    3
+    [4]: 7 / 2
+    3
+    [5]: 3 ** 6
+    217
+    [6]: @[0] - @[1]
+    196
+    [7]: 255 == !0
+    1
+    "#;
+
+    let mut context = Context {
+        backend: Backend::Machine {
+            ty: MachineType::U8,
+            overflow: Overflow::Wrap,
+        },
+        ..Default::default()
+    };
+    let expressions = parse_expressions(CASE);
+    assert_expressions_in(&mut context, &expressions);
+}
+
+#[test]
+fn typed_literals_and_casts() {
+    const CASE: &str = r#"
+    [0]: 255u8
+    255
+    [1]: -128i8
+    -128
+    [2]: 0xffi8
+    -1
+    [3]: !5i64
+    -6
+    [4]: 0x1ff as u8
+    255
+    [5]: -1 as u16
+    65535
+    [6]: (0xff as i8) as i32
+    -1
+    [7]: 300.7 as u8
+    255
+    [8]: 1 << 12 as u8
+    4096
+    [9]: 2pi as i32
+    6
+    [10]: !0x0fu8
+    240
+    [11]: 255u8 + 1u8
+    0
+    [12]: 255u8 + 1
+    0
+    [13]: -0x80i8
+    -128
+    [14]: (200u8 + 100) as u16
+    44
+    [15]: 2u8 ** 9 + 1
+    1
+    [16]: 255u8 + 1 == 0
+    1
+    "#;
+
+    let expressions = parse_expressions(CASE);
+    assert_expressions(&expressions);
+
+    let mut context = Context::<Value>::default();
+    assert_eq!(context.evaluate_annotated("1 + 0.1f32").unwrap(), "1.1");
+    assert_eq!(context.evaluate_annotated("-1i8 :x").unwrap(), "ff");
+    for expr in ["256u8", "-129i8", "0x1ffu8"] {
+        assert!(
+            matches!(
+                context.evaluate(expr),
+                Err(Error::Eval(ValueError::LiteralRange(..)))
+            ),
+            "{expr}"
+        );
+    }
+    assert!(matches!(
+        context.evaluate("1u8 + 1i8"),
+        Err(Error::Eval(ValueError::TypeMismatch(
+            MachineType::U8,
+            MachineType::I8
+        )))
+    ));
+    assert!(matches!(context.evaluate("1 as u7"), Err(Error::Parse(_))));
+    for expr in ["1.5u8", "1e3u8", ".5i32"] {
+        assert!(
+            matches!(
+                context.evaluate(expr),
+                Err(Error::Parse(ParseError::User {
+                    error: UserParseError::IntegerSuffix(_)
+                }))
+            ),
+            "{expr}"
+        );
+    }
+}
+
+#[test]
+fn bit_functions() {
+    const CASE: &str = r#"
+    [0]: popcount(0xff)
+    8
+    [1]: clz(1, 32)
+    31
+    [2]: ctz(0x100)
+    8
+    [3]: bswap(0x12345678, 32) == 0x78563412
+    1
+    [4]: bitrev(0b0001, 4)
+    8
+    [5]: parity(0b111)
+    1
+    [6]: ilog2(1000)
+    9
+    [7]: next_pow2(1000)
+    1024
+    [8]: isolate_lowest(0b101000)
+    8
+    [9]: popcount(-1, 16)
+    16
+    [10]: bswap(0xff00, 16)
+    255
+    "#;
+
+    let expressions = parse_expressions(CASE);
+    assert_expressions(&expressions);
+
+    let mut context = Context::<Value>::default();
+    assert!(matches!(
+        context.evaluate("popcount(1.5)"),
+        Err(Error::Eval(ValueError::ImproperlyFloat))
+    ));
+    assert!(matches!(
+        context.evaluate("ilog2(1, 2)"),
+        Err(Error::Eval(ValueError::Arity(..)))
+    ));
+}
+
+#[test]
+fn bit_slices() {
+    const CASE: &str = r#"
+    [0]: x = 0xabcd
+    43981
+    [1]: x[7:4]
+    12
+    [2]: x[15:12] == 0xa
+    1
+    [3]: x[ 11 : 8 ]
+    11
+    [4]: x[0]
+    1
+    [5]: x[1 + 1]
+    1
+    [6]: (x >> 4)[3:0]
+    12
+    [7]: setbits(x, 7, 4, 0b1010) == 0xabad
+    1
+    [8]: setbits(0, 7, 4, 0b1010)
+    160
+    [9]: 2 * 0xff[3:0]
+    30
+    [10]: getbits(x, 2 * 4 - 1, 4)
+    12
+    "#;
+
+    let expressions = parse_expressions(CASE);
+    assert_expressions(&expressions);
+
+    let mut context = Context::<Value>::default();
+    assert!(matches!(
+        context.evaluate("0xff[0:7]"),
+        Err(Error::Eval(ValueError::BitRange(0, 7)))
+    ));
+    assert!(matches!(
+        context.evaluate("1.5[0]"),
+        Err(Error::Eval(ValueError::ImproperlyFloat))
+    ));
+}
+
+#[test]
+fn machine_overflow() {
+    let machine = |ty, overflow| Context::<Value> {
+        backend: Backend::Machine { ty, overflow },
+        ..Default::default()
+    };
+    let mut saturate = machine(MachineType::I8, Overflow::Saturate);
+    assert_eq!(saturate.evaluate("100 * 2").unwrap(), Value::from(127));
+    assert_eq!(saturate.evaluate("0x80").unwrap(), Value::from(-128));
+    let mut error = machine(MachineType::U16, Overflow::Error);
+    assert_eq!(error.evaluate("0xffff").unwrap(), Value::from(65535));
+    assert!(matches!(
+        error.evaluate("@ + 1"),
+        Err(Error::Eval(ValueError::Arithmetic(
+            ArithmeticError::Overflow
+        )))
+    ));
+    for expr in ["1.5", "sqrt(4)"] {
+        assert!(
+            matches!(
+                error.evaluate(expr),
+                Err(Error::Eval(ValueError::Unsupported(_)))
+            ),
+            "{expr}"
+        );
+    }
+    let mut float = machine(MachineType::F32, Overflow::Wrap);
+    assert_eq!(
+        float.evaluate("0.1 + 0.2").unwrap(),
+        Value::Float(0.3_f32 as f64)
+    );
+    assert_eq!(float.evaluate_annotated("0.1 + 0.2").unwrap(), ".3");
+    assert_eq!(float.evaluate_annotated("1 / 3").unwrap(), ".33333334");
+    assert!(matches!(
+        float.evaluate("7 % 0"),
+        Err(Error::Eval(ValueError::Arithmetic(
+            ArithmeticError::DivideBy0
+        )))
+    ));
+}
+
+#[rstest]
+#[case(MachineType::I64, "2**63", "-9223372036854775808")]
+#[case(MachineType::I64, "-2**63 :x", "8000000000000000")]
+#[case(
+    MachineType::I128,
+    "2**127",
+    "-170141183460469231731687303715884105728"
+)]
+#[case(
+    MachineType::I128,
+    "-2**127",
+    "-170141183460469231731687303715884105728"
+)]
+#[case(MachineType::I8, "-1 :b", "11111111")]
+fn machine_minimum_formats(#[case] ty: MachineType, #[case] expr: &str, #[case] expect: &str) {
+    let mut context = Context::<Quantity> {
+        backend: Backend::Machine {
+            ty,
+            overflow: Overflow::Wrap,
+        },
+        ..Context::new()
+    };
+    assert_eq!(context.evaluate_annotated(expr).unwrap(), expect);
+}
+
+#[test]
+fn complex_numbers() {
+    const CASE: &str = r#"
+    [0]: sqrt(-4)
+    2i
+    [1]: (1+2i)*(3-i)
+    5+5i
+    [2]: i**2
+    -1
+    [3]: abs(3+4i)
+    5
+    [4]: conj(@[1])
+    5-5i
+    [5]: re(@) + im(@)
+    0
+    [6]: arg(-1)
+    3.141592653589793
+    [7]: 1/i
+    -i
+    [8]: max(i**2, 0)
+    0
+    "#;
+
+    let expressions = parse_expressions(CASE);
+    assert_expressions(&expressions);
+
+    let mut context = Context::<Value>::default();
+    for expr in ["min(i, 1)", "max(1, 2, 1+i)", "clamp(i, 0, 1)"] {
+        assert!(
+            matches!(
+                context.evaluate(expr),
+                Err(Error::Eval(ValueError::ImproperlyComplex))
+            ),
+            "{expr}"
+        );
+    }
+}
+
+#[test]
+fn fraction_output() {
+    let mut context = Context::default();
+    for (expr, expect) in [
+        ("1/3 + 1/6", "1/2"),
+        ("-2/3", "-2/3"),
+        ("1/3 :.4", ".3333"),
+        ("1/8 :", "1/8"),
+    ] {
+        assert_eq!(context.evaluate_annotated(expr).unwrap(), expect, "{expr}");
+    }
+}
+
+#[test]
+fn polar_form() {
+    let mut context = Context::default();
+    assert_eq!(
+        context.evaluate_annotated("3+4i :polar").unwrap(),
+        "5*e**(.9272952180016122i)"
+    );
+    assert_eq!(
+        context.evaluate_annotated("-2 :polar").unwrap(),
+        "2*e**(3.141592653589793i)"
+    );
+}